@@ -1,6 +1,8 @@
-use std::{collections::HashMap, iter::zip};
+use std::iter::zip;
 
-advent_of_code::solution!(1);
+use advent_of_code::Counter;
+
+advent_of_code::solution!(1, generator: generate);
 
 fn extract(input: &str) -> (Vec<i64>, Vec<i64>) {
     input
@@ -28,32 +30,35 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-fn count_element_function<I>(it: I) -> HashMap<I::Item, usize>
-where
-    I: IntoIterator,
-    I::Item: Eq + core::hash::Hash,
-{
-    let mut result = HashMap::new();
+pub fn part_two(input: &str) -> Option<u32> {
+    let (list1, list2) = extract(input);
 
-    for item in it {
-        *result.entry(item).or_insert(0) += 1;
-    }
+    let counts: Counter<i64> = list2.into_iter().collect();
 
-    result
+    Some(
+        list1
+            .iter()
+            .fold(0, |acc, x| acc + (counts[x] * (*x as usize)) as u32),
+    )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (list1, list2) = extract(input);
-
-    let counts = count_element_function(list2);
+/// Generates `size` pairs of location IDs, with the right list reusing left IDs every now and then.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let mut left = Vec::with_capacity(size);
 
-    Some(list1.iter().fold(0, |acc, x| {
-        acc + (if counts.contains_key(x) {
-            (counts[x] * (*x as usize)) as u32
-        } else {
-            0
+    (0..size)
+        .map(|_| {
+            let l = rng.range(10000, 100000);
+            left.push(l);
+            let r = if rng.one_in(3) {
+                rng.pick(&left)
+            } else {
+                rng.range(10000, 100000)
+            };
+            format!("{l}   {r}\n")
         })
-    }))
+        .collect()
 }
 
 #[cfg(test)]
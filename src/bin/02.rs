@@ -29,7 +29,7 @@ fn are_all_elements_equal<T: PartialEq>(elems: &[T]) -> bool {
 fn accumulate_safe(acc: usize, data: &[u32]) -> usize {
     let diffs = data
         .windows(2)
-        .map(|p| (p[0] as i64 - p[1] as i64))
+        .map(|p| p[0] as i64 - p[1] as i64)
         .collect::<Vec<_>>();
 
     let signs = diffs.iter().map(|x| x.signum()).collect::<Vec<_>>();
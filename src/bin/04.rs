@@ -92,8 +92,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 fn mtc(c1: char, c2: char) -> bool {
-    (c1.to_ascii_lowercase() == 'm' && c2.to_ascii_lowercase() == 's')
-        || (c1.to_ascii_lowercase() == 's' && c2.to_ascii_lowercase() == 'm')
+    (c1.eq_ignore_ascii_case(&'m') && c2.eq_ignore_ascii_case(&'s'))
+        || (c1.eq_ignore_ascii_case(&'s') && c2.eq_ignore_ascii_case(&'m'))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -103,7 +103,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     for row in 1..data.len() - 1 {
         for col in 1..data[row].len() - 1 {
-            if data[row][col].to_ascii_lowercase() != 'a' {
+            if !data[row][col].eq_ignore_ascii_case(&'a') {
                 continue;
             }
 
@@ -37,7 +37,7 @@ fn parse(input: &str) -> (HashMap<u32, Vec<u32>>, Vec<Vec<u32>>) {
     (page_ordering_rules, update)
 }
 
-fn update_is_valid(update: &Vec<u32>, orders: &HashMap<u32, Vec<u32>>) -> bool {
+fn update_is_valid(update: &[u32], orders: &HashMap<u32, Vec<u32>>) -> bool {
     for (i, u) in update.iter().enumerate() {
         if let Some(order) = orders.get(u) {
             for o in order {
@@ -65,8 +65,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-fn sub_reorder_update(update: &Vec<u32>, orders: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
-    let mut result = update.clone();
+fn sub_reorder_update(update: &[u32], orders: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    let mut result = update.to_vec();
 
     for (i, u) in update.iter().enumerate() {
         if let Some(order) = orders.get(u) {
@@ -81,8 +81,8 @@ fn sub_reorder_update(update: &Vec<u32>, orders: &HashMap<u32, Vec<u32>>) -> Vec
     result
 }
 
-fn reorder_update(update: &Vec<u32>, orders: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
-    let mut result = update.clone();
+fn reorder_update(update: &[u32], orders: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    let mut result = update.to_vec();
     while !update_is_valid(&result, orders) {
         result = sub_reorder_update(&result, orders);
     }
@@ -1,9 +1,10 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::viz::{self, Frame};
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, generator: generate);
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 struct Position {
@@ -167,7 +168,37 @@ enum VisitedPositions {
     Finite(HashSet<DirectedPosition>),
 }
 
-fn follow_guard(board: &Board) -> VisitedPositions {
+/// The lab with the obstacles, the visited positions and the guard, which is highlighted.
+fn guard_frame(
+    board: &Board,
+    guard: &DirectedPosition,
+    positions: &HashSet<DirectedPosition>,
+) -> Frame {
+    let visited: HashSet<Position> = positions.iter().map(|x| x.position).collect();
+
+    Frame::grid(board.size.row, board.size.col, |row, col| {
+        let position = Position { row, col };
+        if position == guard.position {
+            match guard.direction {
+                Direction::Up => '^',
+                Direction::Right => '>',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+            }
+        } else if board.obstacles.contains(&position) {
+            '#'
+        } else if visited.contains(&position) {
+            'X'
+        } else {
+            '.'
+        }
+    })
+    .highlight([(guard.position.row, guard.position.col)])
+    .caption(format!("{} positions visited", visited.len()))
+}
+
+/// Walks the guard until it leaves the lab or loops, emitting a frame per step if `visualize` is set.
+fn follow_guard(board: &Board, visualize: bool) -> VisitedPositions {
     let mut guard = board.guard.clone();
     let mut positions = HashSet::new();
 
@@ -190,6 +221,10 @@ fn follow_guard(board: &Board) -> VisitedPositions {
             guard.position = next_position;
             positions.insert(guard.clone());
         }
+
+        if visualize {
+            viz::frame(|| guard_frame(board, &guard, &positions));
+        }
     }
 
     VisitedPositions::Finite(positions)
@@ -199,7 +234,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let board = parse(input);
 
     if let Some(board) = board {
-        if let VisitedPositions::Finite(positions) = follow_guard(&board) {
+        if let VisitedPositions::Finite(positions) = follow_guard(&board, true) {
             Some(
                 positions
                     .into_iter()
@@ -216,7 +251,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 fn find_loops(board: Board) -> Option<u32> {
-    if let VisitedPositions::Finite(positions) = follow_guard(&board) {
+    if let VisitedPositions::Finite(positions) = follow_guard(&board, false) {
         Some(
             positions
                 .into_iter()
@@ -230,7 +265,9 @@ fn find_loops(board: Board) -> Option<u32> {
                         let mut proposed_board = board.clone();
                         proposed_board.obstacles.insert(x.position);
 
-                        if let VisitedPositions::Loops(_positions) = follow_guard(&proposed_board) {
+                        if let VisitedPositions::Loops(_positions) =
+                            follow_guard(&proposed_board, false)
+                        {
                             acc + 1
                         } else {
                             acc
@@ -254,6 +291,22 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
 }
 
+/// Generates a `size` x `size` lab with sparse obstacles and the guard facing up somewhere inside.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let guard = (rng.range(0, size), rng.range(0, size));
+
+    advent_of_code::template::generator::grid(size, size, |row, col| {
+        if (row, col) == guard {
+            '^'
+        } else if rng.one_in(12) {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
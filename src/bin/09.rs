@@ -1,4 +1,9 @@
-advent_of_code::solution!(9);
+use advent_of_code::template::viz::{self, Frame};
+
+advent_of_code::solution!(9, generator: generate);
+
+/// Blocks per row when visualizing the disk.
+const DISK_COLS: usize = 100;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BlockType {
@@ -15,7 +20,7 @@ struct Block {
 
 impl Block {
     fn new(id: usize, size: usize) -> Self {
-        if id % 2 == 0 {
+        if id.is_multiple_of(2) {
             Block {
                 type_: BlockType::File,
                 free: 0,
@@ -122,6 +127,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             let r = disk[right].data.clone();
             disk[left].fill(&r);
             disk[right].clear();
+            viz::frame(|| disk_frame(&disk, left, r.len()));
             break;
         }
         right -= 1;
@@ -129,18 +135,47 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(
         disk.iter()
-            .flat_map(|d| {
-                d.data
-                    .iter()
-                    .cloned()
-                    .chain(std::iter::repeat(0).take(d.free))
-            })
+            .flat_map(|d| d.data.iter().cloned().chain(std::iter::repeat_n(0, d.free)))
             .enumerate()
             .map(|(i, x)| x * i)
             .sum::<usize>(),
     )
 }
 
+/// The disk wrapped into rows of [`DISK_COLS`] blocks, showing the last digit of each file ID and `.` for free
+/// space. The last `moved` blocks of the data in `disk[block]` are highlighted.
+fn disk_frame(disk: &[Block], block: usize, moved: usize) -> Frame {
+    let cells: Vec<char> = disk
+        .iter()
+        .flat_map(|d| {
+            d.data
+                .iter()
+                .map(|id| char::from_digit((id % 10) as u32, 10).unwrap())
+                .chain(std::iter::repeat_n('.', d.free))
+        })
+        .collect();
+
+    let start: usize = disk[..block].iter().map(|d| d.data.len() + d.free).sum();
+    let end = start + disk[block].data.len();
+    let cols = cells.len().clamp(1, DISK_COLS);
+
+    Frame::grid(cells.len().div_ceil(cols), cols, |row, col| {
+        cells.get(row * cols + col).copied().unwrap_or(' ')
+    })
+    .highlight((end - moved..end).map(|i| (i / cols, i % cols)))
+}
+
+/// Generates a disk map of `size` files, each followed by a (possibly empty) span of free space.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let mut disk_map: String = (0..size.max(1))
+        .map(|_| format!("{}{}", rng.range(1, 10), rng.range(0, 10)))
+        .collect();
+    disk_map.pop();
+    disk_map.push('\n');
+    disk_map
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
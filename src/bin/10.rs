@@ -2,7 +2,11 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(
+    10,
+    generator: generate,
+    variants: [2 => [part_two, part_two_by_height]],
+);
 
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 struct Coordinate {
@@ -131,6 +135,31 @@ impl Map {
 
         count
     }
+
+    /// Counts the trails ending in each cell by sweeping heights from 9 down to 0,
+    /// summing up the counts of the cells one step higher.
+    fn trail_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.data.len()];
+
+        for height in (0..=9).rev() {
+            for (i, _) in self.data.iter().enumerate().filter(|(_, &h)| h == height) {
+                counts[i] = if height == 9 {
+                    1
+                } else {
+                    let Some(c) = self.index_to_coordinate(i) else {
+                        continue;
+                    };
+                    self.neighbours(&c)
+                        .iter()
+                        .filter(|w| self.get(w) == Some(height + 1))
+                        .map(|w| counts[w.row * self.n_cols + w.col])
+                        .sum()
+                };
+            }
+        }
+
+        counts
+    }
 }
 
 fn parse(input: &str) -> Option<Map> {
@@ -181,6 +210,48 @@ pub fn part_two(input: &str) -> Option<usize> {
     })
 }
 
+fn part_two_by_height(input: &str) -> Option<usize> {
+    parse(input).map(|map| {
+        let counts = map.trail_counts();
+        map.data
+            .iter()
+            .zip(counts)
+            .filter(|(&h, _)| h == 0)
+            .map(|(_, c)| c)
+            .sum()
+    })
+}
+
+/// Generates a `size` x `size` topographic map where each height is one step away from
+/// the cell above or to the left of it, so that hiking trails are plentiful.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let mut heights = vec![vec![0; size]; size];
+
+    for row in 0..size {
+        for col in 0..size {
+            let previous = match (row, col) {
+                (0, 0) => rng.range(0, 10),
+                (0, _) => heights[row][col - 1],
+                (_, 0) => heights[row - 1][col],
+                _ if rng.one_in(2) => heights[row][col - 1],
+                _ => heights[row - 1][col],
+            };
+
+            heights[row][col] = match rng.range(0, 4) {
+                0 => previous.saturating_sub(1),
+                1 => (previous + 1).min(9),
+                _ => rng.range(0, 10),
+            };
+        }
+    }
+
+    heights
+        .iter()
+        .map(|row| row.iter().map(ToString::to_string).collect::<String>() + "\n")
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
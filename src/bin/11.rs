@@ -1,39 +1,27 @@
-use std::{collections::HashMap, mem};
+use std::mem;
+
+use advent_of_code::FastCounter;
 
 use nom::{
     bytes::complete::tag, character::complete::digit1, combinator::map_res, multi::separated_list1,
     IResult,
 };
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, generator: generate);
 
 fn parse(input: &str) -> IResult<&str, Vec<usize>> {
     separated_list1(tag(" "), map_res(digit1, |s: &str| s.parse::<usize>()))(input)
 }
 
-macro_rules! update_or_create {
-    ($map:expr, $key:expr, $val:expr) => {{
-        $map.entry($key)
-            .and_modify(|x| {
-                *x += $val;
-            })
-            .or_insert($val);
-    }};
-}
-
 fn count(data: Vec<usize>, n: usize) -> usize {
-    let mut counts: HashMap<usize, usize> = HashMap::new();
-
-    for x in data {
-        update_or_create!(counts, x, 1);
-    }
+    let mut counts: FastCounter<usize> = data.into_iter().collect();
 
     for _ in 0..n {
-        let mut new: HashMap<usize, usize> = HashMap::new();
+        let mut new = FastCounter::default();
 
-        for (&k, &v) in counts.iter() {
+        for (&k, v) in counts.iter() {
             if k == 0 {
-                update_or_create!(new, 1, v);
+                new.add_n(1, v);
             } else {
                 let digits = k.ilog10() + 1;
 
@@ -43,10 +31,10 @@ fn count(data: Vec<usize>, n: usize) -> usize {
                     let left = k / pow;
                     let right = k - left * pow;
 
-                    update_or_create!(new, left, v);
-                    update_or_create!(new, right, v);
+                    new.add_n(left, v);
+                    new.add_n(right, v);
                 } else {
-                    update_or_create!(new, k * 2024, v);
+                    new.add_n(k * 2024, v);
                 }
             }
         }
@@ -55,7 +43,7 @@ fn count(data: Vec<usize>, n: usize) -> usize {
         mem::swap(&mut counts, &mut new);
     }
 
-    counts.values().sum()
+    counts.total()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -74,6 +62,15 @@ pub fn part_two(input: &str) -> Option<usize> {
     }
 }
 
+/// Generates a line of `size` stones with engraved numbers of up to six digits.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let stones: Vec<String> = (0..size.max(1))
+        .map(|_| rng.range(0, 1_000_000).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +86,16 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(65601038650482));
     }
+
+    #[cfg(feature = "dhat-heap")]
+    #[test]
+    fn test_count_allocations() {
+        use advent_of_code::template::allocations::{assert_allocations, Budget};
+
+        let budget = Budget {
+            max_blocks: Some(400),
+            max_bytes: Some(4096),
+        };
+        assert_allocations(budget, || count(vec![125, 17], 75));
+    }
 }
@@ -1,8 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::template::viz::{self, Frame};
 use num::complex::Complex;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, generator: generate);
 
 #[derive(Debug)]
 struct Results {
@@ -103,13 +104,36 @@ fn search(
     )
 }
 
+/// The garden with the plots of all regions found so far highlighted.
+fn garden_frame(
+    map: &ComplexMap,
+    visited: &HashSet<Complex<i64>>,
+    plant: char,
+    r: &Results,
+) -> Frame {
+    // the border of `#` lies at the row and column after the last plot.
+    let rows = map.keys().map(|c| c.re).max().unwrap_or(0) as usize;
+    let cols = map.keys().map(|c| c.im).max().unwrap_or(0) as usize;
+
+    Frame::grid(rows, cols, |row, col| {
+        map[&Complex::new(row as i64, col as i64)]
+    })
+    .highlight(visited.iter().map(|c| (c.re as usize, c.im as usize)))
+    .caption(format!(
+        "region of {plant}: area {}, perimeter {}",
+        r.area, r.perimeter
+    ))
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     parse_complex(input).map(|map| {
         let mut visited = HashSet::new();
         map.iter()
             .filter_map(|(c, v)| {
                 if !visited.contains(c) && map[c] != '#' {
-                    Some(search(&map, c, &mut visited, *v, &Complex::new(1, 0)))
+                    let r = search(&map, c, &mut visited, *v, &Complex::new(1, 0));
+                    viz::frame(|| garden_frame(&map, &visited, *v, &r));
+                    Some(r)
                 } else {
                     None
                 }
@@ -133,6 +157,26 @@ pub fn part_two(input: &str) -> Option<usize> {
     })
 }
 
+/// Generates a `size` x `size` garden where plots tend to share the plant of a neighbour,
+/// so that regions of various shapes and sizes emerge.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+    let plants = ['A', 'B', 'C', 'D', 'E', 'F'];
+    let mut garden: Vec<Vec<char>> = vec![vec!['A'; size]; size];
+
+    for row in 0..size {
+        for col in 0..size {
+            garden[row][col] = match rng.range(0, 5) {
+                0 if row > 0 => garden[row - 1][col],
+                1 | 2 if col > 0 => garden[row][col - 1],
+                _ => rng.pick(&plants),
+            };
+        }
+    }
+
+    advent_of_code::template::generator::grid(size, size, |row, col| garden[row][col])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,7 @@
 use std::cmp::min;
 
+use advent_of_code::trace;
+
 use nom::{
     bytes::complete::tag,
     character::complete::{digit1, multispace1},
@@ -9,7 +11,11 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(
+    13,
+    generator: generate,
+    variants: [1 => [part_one, naive_part_one]],
+);
 
 #[derive(Debug)]
 struct ButtonSettings {
@@ -154,6 +160,7 @@ fn improved_find_minimum_token(machine: &Machine) -> Option<usize> {
     if di % d == 0 && dj % d == 0 {
         Some((3 * di / d + dj / d) as usize)
     } else {
+        trace!("no solution", d = d, di = di, dj = dj);
         None
     }
     // // singular
@@ -211,6 +218,43 @@ pub fn part_one(input: &str) -> Option<usize> {
     }
 }
 
+fn naive_part_one(input: &str) -> Option<usize> {
+    if let Ok((_rest, data)) = parse(input) {
+        Some(data.iter().filter_map(naive_find_minimum_token).sum())
+    } else {
+        None
+    }
+}
+
+/// Generates `size` claw machines whose buttons are never collinear, with prizes that are
+/// usually reachable within the 100 presses allowed in part one.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::generator::Rng::new(seed);
+
+    (0..size)
+        .map(|_| loop {
+            let (ax, ay) = (rng.range(1, 100), rng.range(1, 100));
+            let (bx, by) = (rng.range(1, 100), rng.range(1, 100));
+            if ax * by == ay * bx {
+                continue;
+            }
+
+            let (n_a, n_b) = (rng.range(0, 100), rng.range(0, 100));
+            let mut px = ax * n_a + bx * n_b;
+            let mut py = ay * n_a + by * n_b;
+            if rng.one_in(3) {
+                px += rng.range(0, 10);
+                py += rng.range(0, 10);
+            }
+
+            break format!(
+                "Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}\n"
+            );
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub fn part_two(input: &str) -> Option<usize> {
     let offset = 10000000000000;
     if let Ok((_rest, data)) = parse(input) {
@@ -0,0 +1,281 @@
+/// A multiset that counts how often each key occurs, like Python's `collections.Counter`.
+use std::borrow::Borrow;
+use std::collections::hash_map::{self, RandomState};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::iter::Map;
+use std::ops::{Add, AddAssign, Index, Sub, SubAssign};
+
+use rustc_hash::FxBuildHasher;
+
+/// Counts how often each key occurs. Keys that were never added have a count of 0.
+///
+/// ```ignore
+/// let counter: Counter<char> = "abracadabra".chars().collect();
+/// assert_eq!(counter[&'a'], 5);
+/// assert_eq!(counter.most_common()[0], (&'a', 5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counter<K, S = RandomState> {
+    counts: HashMap<K, usize, S>,
+}
+
+/// A [`Counter`] with a faster, non-cryptographic hash. A good fit for integer keys.
+pub type FastCounter<K> = Counter<K, FxBuildHasher>;
+
+impl<K> Counter<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K, S: Default> Default for Counter<K, S> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher> Counter<K, S> {
+    /// Counts one occurrence of `key`.
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    /// Counts `n` occurrences of `key`.
+    pub fn add_n(&mut self, key: K, n: usize) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// Adds the counts of `other` to this counter.
+    pub fn merge(&mut self, other: Self) {
+        for (key, n) in other {
+            self.add_n(key, n);
+        }
+    }
+
+    /// The number of occurrences of `key`.
+    pub fn get<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Removes `key`, returning its number of occurrences.
+    pub fn remove<Q>(&mut self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// The keys with their counts, from most to least common. Keys with the same count are in no particular order.
+    pub fn most_common(&self) -> Vec<(&K, usize)> {
+        let mut counts: Vec<_> = self.iter().collect();
+        counts.sort_unstable_by_key(|&(_, n)| std::cmp::Reverse(n));
+        counts
+    }
+}
+
+impl<K, S> Counter<K, S> {
+    /// The number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The sum of all counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The keys with their counts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.into_iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = usize> + '_ {
+        self.counts.values().copied()
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher + Default> FromIterator<K> for Counter<K, S> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::default();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher> Extend<K> for Counter<K, S> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+impl<K, S> IntoIterator for Counter<K, S> {
+    type Item = (K, usize);
+    type IntoIter = hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<'a, K, S> IntoIterator for &'a Counter<K, S> {
+    type Item = (&'a K, usize);
+    type IntoIter = Map<hash_map::Iter<'a, K, usize>, fn((&'a K, &'a usize)) -> (&'a K, usize)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter().map(|(key, n)| (key, *n))
+    }
+}
+
+impl<K, Q, S> Index<&Q> for Counter<K, S>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+    S: BuildHasher,
+{
+    type Output = usize;
+
+    fn index(&self, key: &Q) -> &usize {
+        self.counts.get(key).unwrap_or(&0)
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher> PartialEq for Counter<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher> Eq for Counter<K, S> {}
+
+impl<K: Eq + Hash, S: BuildHasher> AddAssign for Counter<K, S> {
+    fn add_assign(&mut self, other: Self) {
+        self.merge(other);
+    }
+}
+
+impl<K: Eq + Hash, S: BuildHasher> Add for Counter<K, S> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Subtracts counts, dropping keys whose count reaches 0.
+impl<K: Eq + Hash, S: BuildHasher> SubAssign for Counter<K, S> {
+    fn sub_assign(&mut self, other: Self) {
+        for (key, n) in other {
+            if let hash_map::Entry::Occupied(mut entry) = self.counts.entry(key) {
+                if *entry.get() > n {
+                    *entry.get_mut() -= n;
+                } else {
+                    entry.remove();
+                }
+            }
+        }
+    }
+}
+
+/// Subtracts counts, dropping keys whose count reaches 0.
+impl<K: Eq + Hash, S: BuildHasher> Sub for Counter<K, S> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Counter, FastCounter};
+
+    #[test]
+    fn counts_keys() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        counter.add('z');
+        counter.add_n('b', 3);
+        counter.add_n('y', 0);
+
+        assert_eq!(counter[&'a'], 5);
+        assert_eq!(counter.get(&'b'), 5);
+        assert_eq!(counter.get(&'y'), 0);
+        assert_eq!(counter[&'q'], 0);
+        assert_eq!(counter.len(), 6);
+        assert_eq!(counter.total(), 15);
+    }
+
+    #[test]
+    fn borrows_keys() {
+        let counter: Counter<String> = ["a", "b", "a"].into_iter().map(String::from).collect();
+
+        assert_eq!(counter["a"], 2);
+        assert_eq!(counter.get("c"), 0);
+    }
+
+    #[test]
+    fn sorts_by_most_common() {
+        let counter: FastCounter<u32> = [3, 1, 3, 2, 3, 1].into_iter().collect();
+
+        assert_eq!(counter.most_common(), [(&3, 3), (&1, 2), (&2, 1)]);
+    }
+
+    #[test]
+    fn merges_and_extends() {
+        let mut counter: Counter<u8> = [1, 2].into_iter().collect();
+        counter.merge([2, 3].into_iter().collect());
+        counter.extend([3, 3]);
+
+        assert_eq!(counter, [1, 2, 2, 3, 3, 3].into_iter().collect());
+    }
+
+    #[test]
+    fn adds_and_subtracts() {
+        let a: Counter<u8> = [1, 1, 2].into_iter().collect();
+        let b: Counter<u8> = [1, 2, 2, 3].into_iter().collect();
+
+        assert_eq!(
+            a.clone() + b.clone(),
+            [1, 1, 1, 2, 2, 2, 3].into_iter().collect()
+        );
+        assert_eq!(a.clone() - b.clone(), [1].into_iter().collect());
+        assert_eq!(b - a, [2, 3].into_iter().collect());
+    }
+
+    #[test]
+    fn iterates_counts() {
+        let counter: Counter<u8> = [1, 1, 2].into_iter().collect();
+
+        let mut counts: Vec<_> = counter.iter().collect();
+        counts.sort_unstable();
+        assert_eq!(counts, [(&1, 2), (&2, 1)]);
+
+        let mut owned: Vec<_> = counter.into_iter().collect();
+        owned.sort_unstable();
+        assert_eq!(owned, [(1, 2), (2, 1)]);
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 
+pub mod counter;
 pub mod template;
 
+pub use counter::{Counter, FastCounter};
+
 // Use this file to add helper functions and additional modules.
+/// Counts how often each element occurs. See [`Counter`] for a type with more counting operations.
 pub fn count_element_function<I>(it: I) -> HashMap<I::Item, usize>
 where
     I: IntoIterator,
@@ -1,144 +1,409 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, download, fuzz, read, scaffold, scale, solve, status, time, Error,
+};
+use args::{AppArguments, Cli};
+use clap::{CommandFactory, Parser};
 
+use advent_of_code::template::commands::scaffold::Overwrite;
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::unlock::{self, SystemClock};
+use advent_of_code::template::{export, Day, DaySet};
+use std::path::PathBuf;
+use std::{io, process};
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{viz, Day, DaySet};
+    use clap::{Parser, Subcommand, ValueEnum};
+    use clap_complete::Shell;
+
+    const DAYS_HELP: &str =
+        "Days to run, e.g. `3`, `3,7,9`, `1..=5`, `1..6` or `all`. Like in Rust, `..` excludes the end of a range and `..=` includes it";
 
+    /// Examples of every command, shown by `help`.
+    const EXAMPLES: &str = "\
+Examples:
+  cargo scaffold 1 --download         Create the files of day 1 and download its input
+  cargo download 1..=3                Download the inputs and descriptions of days 1 to 3
+  cargo read 1                        Print the description of day 1
+  cargo solve 1 --submit 2            Run day 1 and submit the answer of part 2
+  cargo solve 3,7 --release           Run days 3 and 7 with an optimized build
+  cargo solve 4 --profile cpu         Write a CPU flamegraph of day 4 to profiles/
+  cargo solve 6 --visualize gif       Write an animation of day 6 to visualizations/
+  cargo solve 13 --trace              Write the trace events of day 13 to data/traces/
+  cargo all --release                 Run all solutions with an optimized build
+  cargo time all --store              Benchmark all days and store the timings in the readme
+  cargo time --export csv out.csv     Export the stored benchmarks without running solutions
+  cargo fuzz-day 13 --runs 500        Compare the solution variants of day 13 on random inputs
+  cargo scale 9 --budget-ms 500       Measure how day 9 scales with the input size
+  cargo status --readme               Show and store the progress
+  cargo today --wait                  Scaffold, download and read the puzzle of today
+  cargo completions zsh               Print a completion script for zsh";
+
+    #[derive(Parser)]
+    #[command(
+        name = "advent_of_code",
+        about = "Scaffold, run, benchmark and submit Advent of Code solutions.",
+        after_help = EXAMPLES
+    )]
+    pub struct Cli {
+        #[command(subcommand)]
+        pub command: AppArguments,
+    }
+
+    #[derive(Subcommand)]
     pub enum AppArguments {
+        /// Download the inputs and descriptions of days.
+        #[command(after_help = "Example: cargo download 1..=3")]
         Download {
-            day: Day,
+            #[arg(value_name = "DAYS", help = DAYS_HELP)]
+            days: DaySet,
         },
+        /// Print the descriptions of days to the terminal.
+        #[command(after_help = "Example: cargo read 1")]
         Read {
-            day: Day,
+            #[arg(value_name = "DAYS", help = DAYS_HELP)]
+            days: DaySet,
         },
+        /// Create the solution, input and example files of days.
+        #[command(after_help = "Example: cargo scaffold 1 --template grid --return-type u64")]
         Scaffold {
-            day: Day,
+            #[arg(value_name = "DAYS", help = DAYS_HELP)]
+            days: DaySet,
+            /// Download the input and description after scaffolding.
+            #[arg(long)]
             download: bool,
+            /// Replace an existing solution, backing it up to `<path>.bak` (or `.bak.<n>`).
+            #[arg(long)]
             overwrite: bool,
+            /// Replace an existing input, backing it up to `<path>.bak` (or `.bak.<n>`).
+            #[arg(long)]
+            overwrite_input: bool,
+            /// Replace an existing example, backing it up to `<path>.bak` (or `.bak.<n>`).
+            #[arg(long)]
+            overwrite_example: bool,
+            /// Template to create the solution from, built-in or from `templates/<name>.txt`.
+            #[arg(long, value_name = "NAME")]
+            template: Option<String>,
+            /// Return type of both parts.
+            #[arg(long, value_name = "TYPE")]
+            return_type: Option<String>,
         },
+        /// Run the solutions of days against their inputs.
+        #[command(after_help = "Example: cargo solve 1 --submit 1")]
         Solve {
-            day: Day,
+            #[arg(value_name = "DAYS", help = DAYS_HELP)]
+            days: DaySet,
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
+            /// Profile heap allocations with DHAT.
+            #[arg(long)]
             dhat: bool,
+            /// Sample the CPU usage of each part and write a flamegraph to `profiles/`.
+            #[arg(long, value_name = "KIND", conflicts_with = "dhat")]
+            profile: Option<ProfileKind>,
+            /// Play back the frames that the solution emits, in the terminal or as a GIF in `visualizations/`.
+            #[arg(
+                long,
+                value_name = "FORMAT",
+                num_args = 0..=1,
+                default_missing_value = "terminal",
+                conflicts_with_all = ["dhat", "profile"]
+            )]
+            visualize: Option<viz::Format>,
+            /// Report the peak heap usage of each part.
+            #[arg(long)]
+            memory: bool,
+            /// Write the events of `trace!` to `data/traces/<day>.jsonl`.
+            #[arg(long)]
+            trace: bool,
+            /// Submit the answer of a part via aoc-cli.
+            #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
             submit: Option<u8>,
         },
+        /// Run the solutions of all days.
+        #[command(after_help = "Example: cargo all --release --timeout 60")]
         All {
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
+            /// Kill a solution after this many seconds, `0` to disable. Defaults to 300.
+            #[arg(long, value_name = "SECONDS")]
+            timeout: Option<u64>,
         },
+        /// Benchmark solutions. Without days, benchmarks all days that were not benchmarked yet.
+        #[command(after_help = "Example: cargo time 8 --store")]
         Time {
+            #[arg(value_name = "DAYS", help = DAYS_HELP)]
+            days: Option<DaySet>,
+            /// Benchmark all days.
+            #[arg(long)]
             all: bool,
-            day: Option<Day>,
+            /// Store the timings and update the benchmarks in the readme.
+            #[arg(long)]
             store: bool,
+            /// Report the peak heap usage of each part.
+            #[arg(long)]
+            memory: bool,
+            /// Compare each part with its latest stored timing on this machine.
+            #[arg(long)]
+            compare: bool,
+            /// Slowdown in percent that `--compare` flags as a regression. Defaults to 10.
+            #[arg(long, value_name = "PERCENT")]
+            threshold: Option<f64>,
+            /// Benchmark a git revision as well and compare with it.
+            #[arg(long, value_name = "GIT_REF")]
+            baseline: Option<String>,
+            /// Export all known timings as `csv`, `jsonl` or `markdown` to a file. Without days or other flags,
+            /// the stored timings are exported without running any solution.
+            #[arg(long, num_args = 2, value_names = ["FORMAT", "PATH"])]
+            export: Option<Vec<String>>,
+            /// Kill a solution after this many seconds, `0` to disable. Defaults to 300.
+            #[arg(long, value_name = "SECONDS")]
+            timeout: Option<u64>,
+        },
+        /// Compare the solution variants of a day on random inputs.
+        #[command(after_help = "Example: cargo fuzz-day 13 --runs 500 --seed 42")]
+        FuzzDay {
+            day: Day,
+            /// Number of random inputs.
+            #[arg(long)]
+            runs: Option<usize>,
+            /// Seed of the random inputs.
+            #[arg(long)]
+            seed: Option<u64>,
+            /// Maximum size of the random inputs.
+            #[arg(long)]
+            max_size: Option<usize>,
+        },
+        /// Measure how the solution of a day scales with the size of its input.
+        #[command(after_help = "Example: cargo scale 9 --budget-ms 500")]
+        Scale {
+            day: Day,
+            /// Seed of the generated inputs.
+            #[arg(long)]
+            seed: Option<u64>,
+            /// Size of the first generated input, doubled until `--max-size`.
+            #[arg(long)]
+            min_size: Option<usize>,
+            /// Maximum size of the generated inputs.
+            #[arg(long)]
+            max_size: Option<usize>,
+            /// Stop growing the input once a part takes longer than this.
+            #[arg(long, value_name = "MILLIS")]
+            budget_ms: Option<u64>,
         },
+        /// Show the solved parts of every day.
+        #[command(after_help = "Example: cargo status --readme")]
+        Status {
+            /// Update the progress table in the readme.
+            #[arg(long)]
+            readme: bool,
+        },
+        /// Print a shell completion script for the `advent_of_code` binary, see `cargo install --path .`.
+        #[command(after_help = "Example: cargo completions bash > advent_of_code.bash")]
+        Completions { shell: Shell },
+        /// Scaffold, download and read the puzzle of today.
         #[cfg(feature = "today")]
-        Today,
+        #[command(after_help = "Example: cargo today --wait")]
+        Today {
+            /// Wait for the next puzzle with a countdown, unless the puzzle of today was not scaffolded yet.
+            #[arg(long)]
+            wait: bool,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
-        Ok(app_args)
+    /// Kinds of profiles that `solve --profile` records.
+    #[derive(Clone, Copy, ValueEnum)]
+    pub enum ProfileKind {
+        /// Sampled CPU usage, as a flamegraph.
+        Cpu,
     }
 }
 
 fn main() {
-    match parse() {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // `--help` and `help` are printed to stdout and exit successfully.
+        Err(err) if !err.use_stderr() => err.exit(),
         Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+            let _ = err.print();
+            process::exit(Error::Usage(err.to_string()).exit_code());
+        }
+    };
+
+    if let Err(err) = run(cli.command) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All { release, timeout } => all::handle(release, timeout),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            memory,
+            compare,
+            threshold,
+            baseline,
+            export,
+            timeout,
+        } => {
+            let options = time::Options {
+                all,
+                store,
+                memory,
+                compare,
+                threshold,
+                baseline,
+                export: export.map(parse_export).transpose()?,
+                timeout_secs: timeout,
+            };
+            time::handle(days, options)
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
+        AppArguments::Download { days } => for_each_day(&days, download::handle),
+        AppArguments::Read { days } => for_each_day(&days, read::handle),
+        AppArguments::Scaffold {
+            days,
+            download,
+            overwrite,
+            overwrite_input,
+            overwrite_example,
+            template,
+            return_type,
+        } => {
+            let overwrite = Overwrite {
+                module: overwrite,
+                input: overwrite_input,
+                example: overwrite_example,
+            };
+            for_each_day(&days, |day| {
+                scaffold::handle(day, overwrite, template.clone(), return_type.clone())?;
                 if download {
-                    download::handle(day);
+                    download::handle(day)?;
                 }
-            }
-            AppArguments::Solve {
-                day,
+                Ok(())
+            })
+        }
+        AppArguments::Solve {
+            days,
+            release,
+            dhat,
+            profile,
+            visualize,
+            memory,
+            trace,
+            submit,
+        } => {
+            let options = solve::Options {
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
-        },
-    };
+                cpu_profile: matches!(profile, Some(args::ProfileKind::Cpu)),
+                visualize,
+                memory,
+                trace,
+            };
+            solve::handle(&days, options, submit)
+        }
+        AppArguments::FuzzDay {
+            day,
+            runs,
+            seed,
+            max_size,
+        } => fuzz::handle(day, runs, seed, max_size),
+        AppArguments::Scale {
+            day,
+            seed,
+            min_size,
+            max_size,
+            budget_ms,
+        } => scale::handle(day, seed, min_size, max_size, budget_ms),
+        AppArguments::Status { readme } => status::handle(readme),
+        AppArguments::Completions { shell } => {
+            let mut command = Cli::command();
+            clap_complete::generate(shell, &mut command, "advent_of_code", &mut io::stdout());
+            Ok(())
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => {
+            let day = if wait {
+                unlock::wait_for_unlock(
+                    &mut SystemClock,
+                    &mut io::stdout(),
+                    scaffold::is_scaffolded,
+                )
+                .map_err(Error::Usage)?
+            } else {
+                Day::today().ok_or_else(|| {
+                    Error::Usage(
+                        "`today` command can only be run while the puzzles of the event unlock, \
+                        from the 1st to the last day of december. Pass `--wait` to wait for the \
+                        next puzzle, or use `scaffold` with a specific day."
+                            .into(),
+                    )
+                })?
+            };
+
+            scaffold::handle(day, Overwrite::default(), None, None)?;
+            download::handle(day)?;
+            read::handle(day)
+        }
+    }
+}
+
+/// Parse the `<FORMAT> <PATH>` values of `--export`.
+fn parse_export(values: Vec<String>) -> Result<(export::Format, PathBuf), Error> {
+    let [format, path] = <[String; 2]>::try_from(values)
+        .map_err(|_| Error::Usage("`--export` expects a format and a path.".into()))?;
+    Ok((format.parse().map_err(Error::Usage)?, PathBuf::from(path)))
+}
+
+/// Run a command for every day, stopping at the first day that fails.
+fn for_each_day(
+    days: &DaySet,
+    mut handle: impl FnMut(Day) -> Result<(), Error>,
+) -> Result<(), Error> {
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        handle(day)?;
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_export, Cli};
+    use advent_of_code::template::export::Format;
+    use clap::{CommandFactory, Parser};
+
+    #[test]
+    fn verifies_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_export_arguments() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from([&["advent_of_code", "time"], args].concat());
+
+        assert!(parse(&["--export", "csv", "out.csv"]).is_ok());
+        assert!(parse(&["1..=3", "--export", "csv", "out.csv", "--store"]).is_ok());
+        assert!(parse(&["--export", "csv"]).is_err());
+
+        let values = |args: &[&str]| args.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            parse_export(values(&["md", "out.md"])).unwrap(),
+            (Format::Markdown, PathBuf::from("out.md"))
+        );
+        assert!(parse_export(values(&["xlsx", "out.xlsx"])).is_err());
+    }
 }
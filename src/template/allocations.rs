@@ -0,0 +1,51 @@
+/// Allocation budgets for tests, enforced with DHAT by `cargo test --features dhat-heap`.
+///
+/// DHAT counts the allocations of the whole process. Tests run in parallel by default, so the allocations of other
+/// tests can be counted as well. Run the tests with `-- --test-threads=1` for exact numbers.
+use std::sync::Mutex;
+
+/// Only one DHAT profiler can run at a time.
+static PROFILER: Mutex<()> = Mutex::new(());
+
+/// Limits on the heap usage of a function. Limits that are `None` are not checked.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Budget {
+    /// Maximum number of allocations.
+    pub max_blocks: Option<u64>,
+    /// Maximum number of bytes allocated at the same time.
+    pub max_bytes: Option<usize>,
+}
+
+/// Runs `func` under a DHAT testing profiler and asserts that it stays within `budget`.
+///
+/// On failure, the profile is saved to `dhat-heap.json` and can be viewed with
+/// [dh_view](https://nnethercote.github.io/dh_view/dh_view.html).
+///
+/// ```ignore
+/// let budget = Budget { max_blocks: Some(100), ..Budget::default() };
+/// let result = assert_allocations(budget, || part_one(&input));
+/// ```
+pub fn assert_allocations<T>(budget: Budget, func: impl FnOnce() -> T) -> T {
+    let _lock = PROFILER.lock().unwrap_or_else(|e| e.into_inner());
+    let _profiler = dhat::Profiler::builder().testing().build();
+
+    let result = func();
+
+    let stats = dhat::HeapStats::get();
+    if let Some(max_blocks) = budget.max_blocks {
+        dhat::assert!(
+            stats.total_blocks <= max_blocks,
+            "{} allocations, expected at most {max_blocks}",
+            stats.total_blocks
+        );
+    }
+    if let Some(max_bytes) = budget.max_bytes {
+        dhat::assert!(
+            stats.max_bytes <= max_bytes,
+            "peak of {} bytes, expected at most {max_bytes}",
+            stats.max_bytes
+        );
+    }
+
+    result
+}
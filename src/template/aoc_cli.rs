@@ -25,6 +25,32 @@ impl Display for AocCommandError {
     }
 }
 
+/// Result of submitting an answer, as reported by aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    /// The part was already solved, the answer was not checked.
+    AlreadySolved,
+    /// E.g. when an answer was submitted too recently.
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    pub fn from_output(output: &Output) -> Self {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if stdout.contains("That's not the right answer") {
+            SubmissionOutcome::Incorrect
+        } else if stdout.contains("Did you already complete it?") {
+            SubmissionOutcome::AlreadySolved
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+}
+
 pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
@@ -77,7 +103,7 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_captured(&args)
 }
 
 fn get_input_path(day: Day) -> String {
@@ -123,3 +149,21 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Same as [`call_aoc_cli`], but captures stdout so it can be inspected after it was printed.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
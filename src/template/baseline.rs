@@ -0,0 +1,221 @@
+/// Benchmarks the solutions of another git revision, to compare performance across refactors.
+///
+/// The revision is checked out into a temporary git worktree, which receives a copy of the local puzzle inputs
+/// so that both versions are benched on the same data.
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use std::{env, fs, io, process};
+
+use crate::template::commands;
+use crate::template::history::{compare, PartComparison};
+use crate::template::run_multi::run_multi_in;
+use crate::template::timings::{format_duration_nanos, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Git(message) => write!(f, "git failed: {message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A temporary git worktree, removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn create(git_ref: &str) -> Result<Self, Error> {
+        let path = env::temp_dir().join(format!("aoc-baseline-{}", process::id()));
+
+        let output = Command::new("git")
+            .arg("worktree")
+            .arg("add")
+            .arg("--detach")
+            .arg(&path)
+            .arg(git_ref)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::Git(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let _ = Command::new("git")
+            .arg("worktree")
+            .arg("remove")
+            .arg("--force")
+            .arg(&self.path)
+            .output();
+    }
+}
+
+/// Copies the puzzle inputs of the current project, which are usually not committed, into `dir`.
+fn copy_inputs(dir: &Path) -> Result<(), io::Error> {
+    let target = dir.join("data").join("inputs");
+    fs::create_dir_all(&target)?;
+
+    for entry in fs::read_dir(Path::new("data").join("inputs"))? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            fs::copy(entry.path(), target.join(entry.file_name()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the contents of a `Cargo.toml` declare the cargo feature `name`.
+fn declares_feature(manifest: &str, name: &str) -> bool {
+    let mut in_features = false;
+
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_features = line == "[features]";
+        } else if in_features && line.split('=').next().map(str::trim) == Some(name) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Bench the given days of `git_ref` in a temporary worktree.
+pub fn bench(
+    git_ref: &str,
+    days_to_run: &HashSet<Day>,
+    with_memory: bool,
+    timeout: Option<Duration>,
+) -> Result<Timings, commands::Error> {
+    let worktree = Worktree::create(git_ref)?;
+    copy_inputs(&worktree.path).map_err(Error::IO)?;
+
+    println!("{ANSI_BOLD}Baseline ({git_ref}){ANSI_RESET}");
+    println!();
+
+    // revisions from before the `memory` feature can not be built with it.
+    let manifest = fs::read_to_string(worktree.path.join("Cargo.toml")).map_err(Error::IO)?;
+    let with_memory = if with_memory && !declares_feature(&manifest, "memory") {
+        println!("{git_ref} has no `memory` feature, its heap usage is not measured.");
+        println!();
+        false
+    } else {
+        with_memory
+    };
+
+    // failing days of the baseline are reported, but do not fail the comparison.
+    let timings = run_multi_in(
+        Some(&worktree.path),
+        days_to_run,
+        true,
+        true,
+        with_memory,
+        timeout,
+    )?
+    .timings
+    .unwrap_or_default();
+
+    Ok(timings)
+}
+
+/// Formats the change of a part as a speedup factor, e.g. `1.52x faster`.
+fn format_speedup(c: &PartComparison) -> String {
+    if c.after_nanos <= 0.0 {
+        return "-".into();
+    }
+
+    let factor = c.before_nanos / c.after_nanos;
+    if factor >= 1.0 {
+        format!("{factor:.2}x faster")
+    } else {
+        format!("{:.2}x slower", 1.0 / factor)
+    }
+}
+
+/// Print the speedup or slowdown of every part between the baseline and the current version.
+pub fn print_comparison(git_ref: &str, baseline: &Timings, current: &Timings) {
+    println!();
+    println!("{ANSI_BOLD}Comparison with {git_ref}{ANSI_RESET}");
+
+    let comparisons = compare(baseline, current);
+    if comparisons.is_empty() {
+        println!("No parts in common with the baseline.");
+        return;
+    }
+
+    for c in &comparisons {
+        println!(
+            "Day {} Part {}: {} -> {} ({})",
+            c.day,
+            c.part,
+            format_duration_nanos(c.before_nanos),
+            format_duration_nanos(c.after_nanos),
+            format_speedup(c)
+        );
+    }
+
+    let total_before = baseline.total_millis();
+    let total_after = current.total_millis();
+    println!(
+        "Total: {total_before:.2}ms -> {total_after:.2}ms ({:+.1}%)",
+        (total_after - total_before) / total_before.max(f64::EPSILON) * 100.0
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{declares_feature, format_speedup};
+    use crate::{day, template::history::PartComparison};
+
+    fn comparison(before_nanos: f64, after_nanos: f64) -> PartComparison {
+        PartComparison {
+            day: day!(1),
+            part: 1,
+            before_nanos,
+            after_nanos,
+        }
+    }
+
+    #[test]
+    fn formats_speedups() {
+        assert_eq!(format_speedup(&comparison(300.0, 200.0)), "1.50x faster");
+        assert_eq!(format_speedup(&comparison(200.0, 400.0)), "2.00x slower");
+        assert_eq!(format_speedup(&comparison(200.0, 200.0)), "1.00x faster");
+    }
+
+    #[test]
+    fn finds_declared_features() {
+        let manifest = "[package]\nname = \"memory\"\n\n[features]\ntoday = [\"chrono\"]\nmemory = []\n\n[dependencies]\ngif = \"0.13\"\n";
+
+        assert!(declares_feature(manifest, "memory"));
+        assert!(declares_feature(manifest, "today"));
+        assert!(!declares_feature(manifest, "name"));
+        assert!(!declares_feature(manifest, "gif"));
+    }
+}
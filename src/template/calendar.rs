@@ -0,0 +1,280 @@
+/// Summary view of a run over all days, shown as a calendar of five days per row.
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::template::commands::Error;
+use crate::template::timings::{format_duration_nanos, parse_duration_nanos};
+use crate::template::{Day, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Solved,
+    OnePart,
+    /// The solution did not return an answer for any part, e.g. because it panicked.
+    Failed,
+    /// The solution was killed because it exceeded the per-day timeout.
+    TimedOut,
+    NotScaffolded,
+}
+
+/// Why the solution of a day failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The solution exited with a non-zero code (or was killed by a signal), e.g. because it did not compile
+    /// or panicked.
+    Exited(Option<i32>),
+    TimedOut(Duration),
+    /// The listed parts did not return an answer.
+    NoAnswer(Vec<u8>),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Exited(Some(code)) => write!(f, "exited with code {code}"),
+            Failure::Exited(None) => write!(f, "was terminated by a signal"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {}s", timeout.as_secs()),
+            Failure::NoAnswer(parts) if parts.is_empty() => write!(f, "returned no answer"),
+            Failure::NoAnswer(parts) => {
+                let parts: Vec<String> = parts.iter().map(ToString::to_string).collect();
+                let noun = if parts.len() == 1 { "part" } else { "parts" };
+                write!(f, "{noun} {} returned no answer", parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Outcome of running the solution of a single day.
+#[derive(Clone, Debug)]
+pub struct DaySummary {
+    pub day: Day,
+    pub status: DayStatus,
+    /// Combined duration of all parts that returned an answer.
+    pub nanos: Option<f64>,
+    pub failure: Option<Failure>,
+}
+
+impl DaySummary {
+    pub fn not_scaffolded(day: Day) -> Self {
+        Self {
+            day,
+            status: DayStatus::NotScaffolded,
+            nanos: None,
+            failure: None,
+        }
+    }
+
+    /// Summarize the output of a solution bin, e.g. `Part 1: 42 (1.2ms)`.
+    pub fn from_output(day: Day, output: &[String]) -> Self {
+        let parts: Vec<(Option<u8>, Option<f64>)> = output
+            .iter()
+            .filter(|l| l.starts_with("Part "))
+            .map(|l| (parse_part_number(l), parse_part_duration(l)))
+            .collect();
+
+        let solved = parts.iter().filter(|(_, nanos)| nanos.is_some()).count();
+        let status = match solved {
+            0 => DayStatus::Failed,
+            1 if parts.len() > 1 => DayStatus::OnePart,
+            _ => DayStatus::Solved,
+        };
+
+        let unanswered: Vec<u8> = parts
+            .iter()
+            .filter(|(_, nanos)| nanos.is_none())
+            .filter_map(|(part, _)| *part)
+            .collect();
+
+        Self {
+            day,
+            status,
+            nanos: (solved > 0).then(|| parts.iter().filter_map(|(_, nanos)| *nanos).sum()),
+            failure: (solved < parts.len() || parts.is_empty())
+                .then_some(Failure::NoAnswer(unanswered)),
+        }
+    }
+
+    /// Mark the day as failed if its solution did not exit successfully. Solutions exit with the code of
+    /// [`Error::NoAnswer`] if a part returned no answer, which the output already tells.
+    #[must_use]
+    pub fn with_exit_code(mut self, code: Option<i32>) -> Self {
+        let no_answer = code == Some(Error::NoAnswer(vec![]).exit_code())
+            && matches!(self.failure, Some(Failure::NoAnswer(_)));
+
+        if code != Some(0) && !no_answer {
+            self.status = DayStatus::Failed;
+            self.failure = Some(Failure::Exited(code));
+        }
+        self
+    }
+
+    /// Mark the day as timed out, regardless of the parts it solved before it was killed.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.status = DayStatus::TimedOut;
+        self.failure = Some(Failure::TimedOut(timeout));
+        self
+    }
+}
+
+fn parse_part_number(line: &str) -> Option<u8> {
+    line.strip_prefix("Part ")?.split(':').next()?.parse().ok()
+}
+
+/// Parse the duration of a part that returned an answer. Parts without an answer are printed as `✖`.
+fn parse_part_duration(line: &str) -> Option<f64> {
+    if line.contains('✖') {
+        return None;
+    }
+
+    let duration = line.rsplit_once('(')?.1.split([' ', ')']).next()?;
+    parse_duration_nanos(duration)
+}
+
+fn status_cell(status: DayStatus) -> String {
+    match status {
+        DayStatus::Solved => format!("{ANSI_GREEN}★★{ANSI_RESET}"),
+        DayStatus::OnePart => format!("{ANSI_YELLOW}★☆{ANSI_RESET}"),
+        DayStatus::Failed => format!("{ANSI_RED}✖ {ANSI_RESET}"),
+        DayStatus::TimedOut => format!("{ANSI_RED}⧖ {ANSI_RESET}"),
+        DayStatus::NotScaffolded => format!("{ANSI_DIM}· {ANSI_RESET}"),
+    }
+}
+
+/// Colors a runtime from green (below 1ms) over yellow (below 100ms) to red.
+fn runtime_cell(nanos: Option<f64>) -> String {
+    let Some(nanos) = nanos else {
+        return " ".repeat(8);
+    };
+
+    let color = match nanos {
+        n if n < 1e6 => ANSI_GREEN,
+        n if n < 1e8 => ANSI_YELLOW,
+        _ => ANSI_RED,
+    };
+
+    format!("{color}{:>8}{ANSI_RESET}", format_duration_nanos(nanos))
+}
+
+pub fn render(summaries: &[DaySummary]) -> String {
+    summaries
+        .chunks(5)
+        .map(|week| {
+            week.iter()
+                .map(|s| {
+                    format!(
+                        "{:>2} {} {}",
+                        s.day.into_inner(),
+                        status_cell(s.status),
+                        runtime_cell(s.nanos)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn print(summaries: &[DaySummary]) {
+    println!();
+    println!("{ANSI_BOLD}Calendar{ANSI_RESET}");
+    println!();
+    println!("{}", render(summaries));
+}
+
+/// Print the days that failed and why, if any.
+pub fn print_failures(summaries: &[DaySummary]) {
+    let failed: Vec<&DaySummary> = summaries.iter().filter(|s| s.failure.is_some()).collect();
+    if failed.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{ANSI_BOLD}{ANSI_RED}Failed days{ANSI_RESET}");
+    println!();
+    for summary in failed {
+        if let Some(failure) = &summary.failure {
+            println!("Day {}: {failure}", summary.day);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, DayStatus, DaySummary, Failure};
+    use crate::day;
+    use crate::template::{all_days, LAST_DAY};
+
+    fn summarize(lines: &[&str]) -> DaySummary {
+        let output: Vec<String> = lines.iter().map(|l| (*l).to_string()).collect();
+        DaySummary::from_output(day!(1), &output)
+    }
+
+    #[test]
+    fn summarizes_solved_days() {
+        let summary = summarize(&["Part 1: 42 (1.0ms)", "Part 2: 43 (2.0ms @ 10 samples)"]);
+        assert_eq!(summary.status, DayStatus::Solved);
+        assert_eq!(summary.nanos, Some(3_000_000.0));
+        assert_eq!(summary.failure, None);
+    }
+
+    #[test]
+    fn summarizes_partially_solved_days() {
+        let summary = summarize(&["Part 1: (a) (1.5µs)", "Part 2: ✖             "]);
+        assert_eq!(summary.status, DayStatus::OnePart);
+        assert_eq!(summary.nanos, Some(1500.0));
+        assert_eq!(summary.failure, Some(Failure::NoAnswer(vec![2])));
+    }
+
+    #[test]
+    fn summarizes_failed_days() {
+        let summary = summarize(&["Part 1: ✖             ", "Part 2: ✖             "]);
+        assert_eq!(summary.status, DayStatus::Failed);
+        assert_eq!(summary.nanos, None);
+        assert_eq!(summarize(&[]).status, DayStatus::Failed);
+        assert_eq!(
+            summary.failure.unwrap().to_string(),
+            "parts 1, 2 returned no answer"
+        );
+    }
+
+    #[test]
+    fn fails_days_that_exit_unsuccessfully() {
+        let solved = ["Part 1: 42 (1.0ms)", "Part 2: 43 (2.0ms)"];
+        assert_eq!(summarize(&solved).with_exit_code(Some(0)).failure, None);
+
+        let panicked = summarize(&["Part 1: 42 (1.0ms)"]).with_exit_code(Some(101));
+        assert_eq!(panicked.status, DayStatus::Failed);
+        assert_eq!(panicked.failure, Some(Failure::Exited(Some(101))));
+    }
+
+    #[test]
+    fn keeps_unanswered_parts_of_days_that_exit_without_answer() {
+        let summary =
+            summarize(&["Part 1: 42 (1.0ms)", "Part 2: ✖             "]).with_exit_code(Some(12));
+        assert_eq!(summary.status, DayStatus::OnePart);
+        assert_eq!(summary.failure, Some(Failure::NoAnswer(vec![2])));
+    }
+
+    #[test]
+    fn fails_days_that_time_out() {
+        let summary = summarize(&["Part 1: 42 (1.0ms)"]).with_timeout(Duration::from_secs(60));
+        assert_eq!(summary.status, DayStatus::TimedOut);
+        assert_eq!(summary.nanos, Some(1_000_000.0));
+        assert_eq!(summary.failure.unwrap().to_string(), "timed out after 60s");
+    }
+
+    #[test]
+    fn renders_five_days_per_row() {
+        let summaries: Vec<DaySummary> = all_days().map(DaySummary::not_scaffolded).collect();
+        assert_eq!(
+            render(&summaries).lines().count(),
+            usize::from(LAST_DAY).div_ceil(5)
+        );
+    }
+}
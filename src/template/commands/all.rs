@@ -1,5 +1,19 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::all_days;
+use crate::template::commands::Error;
+use crate::template::run_multi::{day_timeout, run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, timeout_secs: Option<u64>) -> Result<(), Error> {
+    let run = run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        false,
+        day_timeout(timeout_secs),
+    )?;
+
+    if run.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Failed(run.failed))
+    }
 }
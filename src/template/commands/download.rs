@@ -1,14 +1,19 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::commands::Error;
+use crate::template::{aoc_cli, manifest, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(day: Day) -> Result<(), Error> {
+    aoc_cli::check()?;
+    aoc_cli::download(day)?;
+
+    match manifest::register(day) {
+        Ok(meta) => {
+            println!("🎄 Registered puzzle \"{}\".", meta.title);
+            if let Ok(true) = manifest::update_module_doc(day, &meta.title) {
+                println!("🎄 Added the title to \"src/bin/{day}.rs\".");
+            }
+        }
+        Err(e) => eprintln!("Failed to register puzzle metadata: {e}"),
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    Ok(())
 }
@@ -0,0 +1,33 @@
+use crate::template::commands::{run_cargo, Error};
+use crate::template::Day;
+
+pub fn handle(
+    day: Day,
+    runs: Option<usize>,
+    seed: Option<u64>,
+    max_size: Option<usize>,
+) -> Result<(), Error> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--fuzz".to_string(),
+    ];
+
+    if let Some(runs) = runs {
+        cmd_args.extend(["--runs".to_string(), runs.to_string()]);
+    }
+
+    if let Some(seed) = seed {
+        cmd_args.extend(["--seed".to_string(), seed.to_string()]);
+    }
+
+    if let Some(max_size) = max_size {
+        cmd_args.extend(["--max-size".to_string(), max_size.to_string()]);
+    }
+
+    run_cargo(&cmd_args)
+}
@@ -1,6 +1,192 @@
+use std::fmt::Display;
+use std::io;
+use std::process::ExitStatus;
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::{baseline, templates, Day};
+
 pub mod all;
 pub mod download;
+pub mod fuzz;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod status;
 pub mod time;
+
+/// Errors of the command handlers. They are rendered by `main`, which exits with [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line input.
+    Usage(String),
+    /// `aoc` is not installed or not callable.
+    AocCliMissing,
+    AocCli(AocCommandError),
+    /// A child process (usually `cargo run` of a solution) exited with a failure.
+    Child {
+        command: String,
+        status: ExitStatus,
+    },
+    /// The solutions of these days did not compile, panicked or did not return an answer for every part.
+    Failed(Vec<Day>),
+    /// A file that should not be replaced exists already.
+    FileExists(String),
+    Template(templates::Error),
+    Baseline(baseline::Error),
+    /// The readme could not be updated.
+    Readme(String),
+    /// Some parts got slower than the stored benchmarks by more than the threshold (in percent).
+    Regression(f64),
+    IO(io::Error),
+    /// The listed parts of a solution returned `None`.
+    NoAnswer(Vec<u8>),
+}
+
+impl Error {
+    /// Exit code of the process, distinct for every kind of failure.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Child { .. } => 1,
+            Error::Usage(_) => 2,
+            Error::AocCliMissing => 3,
+            Error::AocCli(_) => 4,
+            Error::FileExists(_) => 5,
+            Error::Template(_) => 6,
+            Error::Baseline(_) => 7,
+            Error::Readme(_) => 8,
+            Error::Regression(_) => 9,
+            Error::IO(_) => 10,
+            Error::Failed(_) => 11,
+            Error::NoAnswer(_) => 12,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        match e {
+            AocCommandError::CommandNotFound => Error::AocCliMissing,
+            e => Error::AocCli(e),
+        }
+    }
+}
+
+impl From<templates::Error> for Error {
+    fn from(e: templates::Error) -> Self {
+        Error::Template(e)
+    }
+}
+
+impl From<baseline::Error> for Error {
+    fn from(e: baseline::Error) -> Self {
+        Error::Baseline(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{message}"),
+            Error::AocCliMissing => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Child { command, status } => write!(f, "`{command}` failed: {status}"),
+            Error::Failed(days) => {
+                let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+                write!(f, "solutions of day(s) {} failed.", days.join(", "))
+            }
+            Error::FileExists(path) => write!(
+                f,
+                "file \"{path}\" already exists. Pass `--overwrite` to replace it."
+            ),
+            Error::Template(e) => write!(f, "failed to load template: {e}"),
+            Error::Baseline(e) => write!(f, "failed to bench baseline: {e}"),
+            Error::Readme(e) => write!(f, "failed to update readme: {e}"),
+            Error::Regression(threshold) => {
+                write!(f, "some parts regressed by more than {threshold}%.")
+            }
+            Error::IO(e) => write!(f, "{e}"),
+            Error::NoAnswer(parts) => {
+                let parts: Vec<String> = parts.iter().map(ToString::to_string).collect();
+                write!(f, "part(s) {} returned no answer.", parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Run `cargo` with the given arguments, forwarding its output.
+fn run_cargo(args: &[String]) -> Result<(), Error> {
+    let status = std::process::Command::new("cargo")
+        .args(args)
+        .stdout(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::inherit())
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Child {
+            command: format!("cargo {}", args.join(" ")),
+            status,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::process::ExitStatus;
+
+    use super::Error;
+    use crate::template::aoc_cli::AocCommandError;
+    use crate::template::{baseline, templates};
+
+    #[test]
+    fn maps_aoc_cli_errors() {
+        assert!(matches!(
+            Error::from(AocCommandError::CommandNotFound),
+            Error::AocCliMissing
+        ));
+        assert!(matches!(
+            Error::from(AocCommandError::CommandNotCallable),
+            Error::AocCli(_)
+        ));
+    }
+
+    #[test]
+    fn uses_distinct_exit_codes() {
+        let errors = [
+            Error::Usage(String::new()),
+            Error::AocCliMissing,
+            Error::AocCli(AocCommandError::CommandNotCallable),
+            Error::Child {
+                command: String::new(),
+                status: ExitStatus::default(),
+            },
+            Error::Failed(vec![]),
+            Error::FileExists(String::new()),
+            Error::Template(templates::Error::NotFound(String::new())),
+            Error::Baseline(baseline::Error::Git(String::new())),
+            Error::Readme(String::new()),
+            Error::Regression(10.0),
+            Error::IO(std::io::Error::other("")),
+            Error::NoAnswer(vec![]),
+        ];
+
+        let mut codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|c| *c > 0));
+    }
+}
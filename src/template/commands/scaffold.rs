@@ -1,79 +1,206 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::path::Path;
+use std::{fs, io};
 
+use crate::template::commands::Error;
+use crate::template::templates::{self, Placeholders, DEFAULT_TEMPLATE};
 use crate::template::Day;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Which of the existing, non-empty files of a day may be replaced. Replaced files are backed up to `<path>.bak`, or
+/// `<path>.bak.1`, `<path>.bak.2`, ... if earlier backups exist.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Overwrite {
+    pub module: bool,
+    pub input: bool,
+    pub example: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    Keep,
+    Replace,
+}
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Decide what to do with a file, given the size of the existing file, if any.
+/// Empty files are replaced without a backup, as there is nothing to lose.
+fn plan(existing_len: Option<u64>, overwrite: bool) -> Action {
+    match existing_len {
+        None | Some(0) => Action::Create,
+        Some(_) if overwrite => Action::Replace,
+        Some(_) => Action::Keep,
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// The first backup path of `path` that is not taken yet, so that earlier backups are never overwritten.
+fn backup_path(path: &str, exists: impl Fn(&str) -> bool) -> String {
+    std::iter::once(format!("{path}.bak"))
+        .chain((1..).map(|n| format!("{path}.bak.{n}")))
+        .find(|backup| !exists(backup))
+        .unwrap()
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+struct ScaffoldFile {
+    name: &'static str,
+    flag: &'static str,
+    path: String,
+    backup_path: String,
+    contents: String,
+    action: Action,
+    existed: bool,
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+impl ScaffoldFile {
+    fn new(
+        name: &'static str,
+        flag: &'static str,
+        path: String,
+        contents: String,
+        overwrite: bool,
+    ) -> Self {
+        let existing_len = fs::metadata(&path).ok().map(|m| m.len());
+        Self {
+            name,
+            flag,
+            action: plan(existing_len, overwrite),
+            existed: existing_len.is_some(),
+            backup_path: backup_path(&path, |p| Path::new(p).exists()),
+            path,
+            contents,
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    fn apply(&self) -> Result<(), io::Error> {
+        match self.action {
+            Action::Create => fs::write(&self.path, &self.contents),
+            Action::Keep => Ok(()),
+            Action::Replace => {
+                fs::copy(&self.path, &self.backup_path)?;
+                fs::write(&self.path, &self.contents)
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    /// Undo `apply`, restoring the state before the scaffold.
+    fn revert(&self) {
+        let _ = match self.action {
+            Action::Create if self.existed => fs::write(&self.path, ""),
+            Action::Create => fs::remove_file(&self.path),
+            Action::Keep => Ok(()),
+            Action::Replace => fs::rename(&self.backup_path, &self.path),
+        };
+    }
+
+    fn report(&self) {
+        match self.action {
+            Action::Create if self.contents.is_empty() => {
+                println!("Created empty {} file \"{}\"", self.name, self.path);
+            }
+            Action::Create => println!("Created {} file \"{}\"", self.name, self.path),
+            Action::Keep => println!(
+                "Kept existing {} file \"{}\" (pass `{}` to replace it)",
+                self.name, self.path, self.flag
+            ),
+            Action::Replace => println!(
+                "Replaced {} file \"{}\", backed up to \"{}\"",
+                self.name, self.path, &self.backup_path
+            ),
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+}
+
+/// Path of the solution module of a day.
+fn module_path(day: Day) -> String {
+    format!("src/bin/{day}.rs")
+}
+
+/// Whether the solution module of a day exists and is not empty.
+pub fn is_scaffolded(day: Day) -> bool {
+    fs::metadata(module_path(day)).is_ok_and(|m| m.len() > 0)
+}
+
+pub fn handle(
+    day: Day,
+    overwrite: Overwrite,
+    template: Option<String>,
+    return_type: Option<String>,
+) -> Result<(), Error> {
+    let template_name = template.unwrap_or_else(|| DEFAULT_TEMPLATE.into());
+    let module_template = templates::load(&template_name)?;
+
+    let placeholders = Placeholders::new(day, return_type);
+
+    let files = [
+        ScaffoldFile::new(
+            "module",
+            "--overwrite",
+            module_path(day),
+            templates::render(&module_template, &placeholders),
+            overwrite.module,
+        ),
+        ScaffoldFile::new(
+            "input",
+            "--overwrite-input",
+            format!("data/inputs/{day}.txt"),
+            String::new(),
+            overwrite.input,
+        ),
+        ScaffoldFile::new(
+            "example",
+            "--overwrite-example",
+            format!("data/examples/{day}.txt"),
+            String::new(),
+            overwrite.example,
+        ),
+    ];
+
+    // a solution is never kept silently, so that scaffolding a day twice does not look like it succeeded.
+    if files[0].action == Action::Keep {
+        return Err(Error::FileExists(files[0].path.clone()));
+    }
+
+    // apply all changes, or none of them.
+    for (i, file) in files.iter().enumerate() {
+        if let Err(e) = file.apply() {
+            files[..=i].iter().rev().for_each(ScaffoldFile::revert);
+            return Err(Error::IO(io::Error::new(
+                e.kind(),
+                format!("failed to write {} file \"{}\": {e}", file.name, file.path),
+            )));
         }
     }
 
+    files.iter().for_each(ScaffoldFile::report);
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{backup_path, plan, Action};
+
+    #[test]
+    fn creates_missing_and_empty_files() {
+        assert_eq!(plan(None, false), Action::Create);
+        assert_eq!(plan(Some(0), false), Action::Create);
+    }
+
+    #[test]
+    fn keeps_existing_files_unless_overwritten() {
+        assert_eq!(plan(Some(10), false), Action::Keep);
+        assert_eq!(plan(Some(10), true), Action::Replace);
+    }
+
+    #[test]
+    fn never_overwrites_backups() {
+        assert_eq!(backup_path("01.rs", |_| false), "01.rs.bak");
+        assert_eq!(
+            backup_path("01.rs", |p| ["01.rs.bak", "01.rs.bak.1"].contains(&p)),
+            "01.rs.bak.2"
+        );
+    }
 }
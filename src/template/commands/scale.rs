@@ -0,0 +1,38 @@
+use crate::template::commands::{run_cargo, Error};
+use crate::template::Day;
+
+pub fn handle(
+    day: Day,
+    seed: Option<u64>,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
+    budget_ms: Option<u64>,
+) -> Result<(), Error> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--scale".to_string(),
+    ];
+
+    if let Some(seed) = seed {
+        cmd_args.extend(["--seed".to_string(), seed.to_string()]);
+    }
+
+    if let Some(min_size) = min_size {
+        cmd_args.extend(["--min-size".to_string(), min_size.to_string()]);
+    }
+
+    if let Some(max_size) = max_size {
+        cmd_args.extend(["--max-size".to_string(), max_size.to_string()]);
+    }
+
+    if let Some(budget_ms) = budget_ms {
+        cmd_args.extend(["--budget-ms".to_string(), budget_ms.to_string()]);
+    }
+
+    run_cargo(&cmd_args)
+}
@@ -1,34 +1,84 @@
-use std::process::{Command, Stdio};
+use crate::template::commands::{run_cargo, Error};
+use crate::template::{viz, Day, DaySet};
 
-use crate::template::Day;
+/// How the solutions are built and run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    pub cpu_profile: bool,
+    pub visualize: Option<viz::Format>,
+    pub memory: bool,
+    pub trace: bool,
+}
+
+pub fn handle(days: &DaySet, options: Options, submit_part: Option<u8>) -> Result<(), Error> {
+    if submit_part.is_some() && days.len() > 1 {
+        return Err(Error::Usage(
+            "`--submit` can only be used with a single day.".into(),
+        ));
+    }
+
+    // with several days, keep running the remaining days if one of them fails.
+    let mut failed = vec![];
+
+    for day in days {
+        match solve_day(day, options, submit_part) {
+            Err(Error::Child { .. }) if days.len() > 1 => failed.push(day),
+            result => result?,
+        }
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Failed(failed))
+    }
+}
+
+fn solve_day(day: Day, options: Options, submit_part: Option<u8>) -> Result<(), Error> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if dhat {
+    if options.dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
+    } else if options.cpu_profile {
+        cmd_args.extend([
+            "--profile".to_string(),
+            "cpu".to_string(),
+            "--features".to_string(),
+            "cpu-profile".to_string(),
+        ]);
+    } else if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.memory && !options.dhat {
+        cmd_args.extend(["--features".to_string(), "memory".to_string()]);
+    }
+
+    if options.visualize.is_some() {
+        cmd_args.extend(["--features".to_string(), "visualize".to_string()]);
+    }
+
+    if options.trace {
+        cmd_args.extend(["--features".to_string(), "trace".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(format) = options.visualize {
+        cmd_args.extend(["--visualize".to_string(), format.to_string()]);
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
-
-    cmd.wait().unwrap();
+    run_cargo(&cmd_args)
 }
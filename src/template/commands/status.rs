@@ -0,0 +1,16 @@
+use crate::template::commands::Error;
+use crate::template::manifest::Manifest;
+use crate::template::progress::{self, Progress};
+
+pub fn handle(update_readme: bool) -> Result<(), Error> {
+    let progress = Progress::read_from_file()?;
+    progress::print_status(&progress, &Manifest::read_from_file());
+
+    if update_readme {
+        println!();
+        progress::update_readme(&progress).map_err(Error::Readme)?;
+        println!("Updated progress in readme.");
+    }
+
+    Ok(())
+}
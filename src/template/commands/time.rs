@@ -1,15 +1,57 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::baseline;
+use crate::template::commands::Error;
+use crate::template::export::{self, Format};
+use crate::template::history::{self, HistoryEntry};
+use crate::template::run_multi::{day_timeout, run_multi};
+use crate::template::timings::{format_duration_nanos, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, DaySet, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Parts that got slower by more than this are flagged by `--compare` unless `--threshold` is passed.
+const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+/// Which days are benched, and what happens with their timings.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Bench days whose timings are complete as well.
+    pub all: bool,
+    pub store: bool,
+    pub memory: bool,
+    pub compare: bool,
+    pub threshold: Option<f64>,
+    pub baseline: Option<String>,
+    pub export: Option<(Format, PathBuf)>,
+    pub timeout_secs: Option<u64>,
+}
+
+pub fn handle(days: Option<DaySet>, options: Options) -> Result<(), Error> {
+    let Options {
+        all: run_all,
+        store,
+        memory: with_memory,
+        compare,
+        threshold,
+        baseline,
+        export,
+        timeout_secs,
+    } = options;
+
+    let timeout = day_timeout(timeout_secs);
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    // exporting alone does not bench the days that are missing from the stored timings.
+    let export_only = days.is_none() && !(run_all || store || compare || baseline.is_some());
+    if let (Some((format, path)), true) = (&export, export_only) {
+        export::write(&stored_timings, *format, path)?;
+        println!("Exported stored timings to {}.", path.display());
+        return Ok(());
+    }
+
+    let days_to_run: HashSet<Day> = days.map_or_else(
         || {
-            if run_all {
+            if run_all || compare || baseline.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -18,23 +60,99 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.iter().collect(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let baseline_timings = match &baseline {
+        Some(git_ref) => {
+            let timings = baseline::bench(git_ref, &days_to_run, with_memory, timeout)?;
+            println!();
+            Some(timings)
+        }
+        None => None,
+    };
+
+    let run = run_multi(&days_to_run, true, true, with_memory, timeout)?;
+    let timings = run.timings.unwrap_or_default();
+
+    if let (Some(git_ref), Some(baseline_timings)) = (&baseline, &baseline_timings) {
+        baseline::print_comparison(git_ref, baseline_timings, &timings);
+    }
+
+    let threshold = threshold.unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
+    let has_regressions = compare && compare_with_history(&timings, threshold);
+
+    if let Some((format, path)) = export {
+        // export all known timings, not only the days that were benched by this run.
+        let merged_timings = stored_timings.merge(&timings);
+        export::write(&merged_timings, format, &path)?;
+        println!("Exported timings to {}.", path.display());
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file()?;
+
+        history::append(&HistoryEntry::now(timings))?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
-        }
+        readme_benchmarks::update(merged_timings).map_err(|e| Error::Readme(e.to_string()))?;
+        println!("Stored updated benchmarks.");
     }
+
+    if !run.failed.is_empty() {
+        return Err(Error::Failed(run.failed));
+    }
+
+    if has_regressions {
+        return Err(Error::Regression(threshold));
+    }
+
+    Ok(())
+}
+
+/// Compare timings with the latest stored timing of each part on this machine. Returns whether any part regressed.
+fn compare_with_history(timings: &Timings, threshold: f64) -> bool {
+    let entries = history::read_all();
+    let machine = history::machine_id();
+
+    println!();
+
+    let Some(previous) = history::latest_for_machine(&entries, &machine) else {
+        println!("No stored benchmark run for machine \"{machine}\" to compare with.");
+        return false;
+    };
+
+    println!("{ANSI_BOLD}Comparison with previous runs{ANSI_RESET} (machine \"{machine}\"):");
+
+    let comparisons = history::compare(&previous, timings);
+    let mut has_regressions = false;
+
+    for c in &comparisons {
+        let change = c.change_percent();
+        let flag = if c.is_regression(threshold) {
+            has_regressions = true;
+            format!(" {ANSI_BOLD}regressed{ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {} Part {}: {} -> {} ({change:+.1}%){flag}",
+            c.day,
+            c.part,
+            format_duration_nanos(c.before_nanos),
+            format_duration_nanos(c.after_nanos)
+        );
+    }
+
+    if comparisons.is_empty() {
+        println!("No parts in common with previous runs.");
+    } else if has_regressions {
+        println!("Some parts regressed by more than {threshold}%.");
+    } else {
+        println!("No part regressed by more than {threshold}%.");
+    }
+
+    has_regressions
 }
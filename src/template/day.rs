@@ -1,14 +1,66 @@
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
 
+/// Offset of the timezone of the Advent of Code servers to UTC, in hours. Puzzles unlock at midnight there.
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+pub(crate) const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The year of the event, set with `AOC_YEAR` in `.cargo/config.toml`.
+pub const EVENT_YEAR: Option<u16> = match option_env!("AOC_YEAR") {
+    Some(year) => Some(parse_const(year)),
+    None => None,
+};
+
+/// The last day of the event. Events until 2024 have 25 days, events since 2025 have 12 days.
+/// Other calendars can set their number of days with `AOC_DAYS` in `.cargo/config.toml`.
+pub const LAST_DAY: u8 = last_day(option_env!("AOC_DAYS"), EVENT_YEAR);
+
+const fn last_day(days: Option<&str>, year: Option<u16>) -> u8 {
+    match (days, year) {
+        (Some(days), _) => {
+            let days = parse_const(days);
+            assert!(
+                days >= 1 && days <= 99,
+                "`AOC_DAYS` must be between 1 and 99"
+            );
+            #[allow(clippy::cast_possible_truncation)]
+            let days = days as u8;
+            days
+        }
+        (None, Some(year)) if year >= 2025 => 12,
+        (None, _) => 25,
+    }
+}
+
+/// Parses a number from an environment variable at compile time.
+const fn parse_const(value: &str) -> u16 {
+    let bytes = value.as_bytes();
+    assert!(
+        !bytes.is_empty(),
+        "`AOC_YEAR` and `AOC_DAYS` must be numbers"
+    );
+
+    let mut number: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let digit = bytes[i];
+        assert!(
+            digit.is_ascii_digit() && number <= (u16::MAX - 9) / 10,
+            "`AOC_YEAR` and `AOC_DAYS` must be numbers"
+        );
+        number = number * 10 + (digit - b'0') as u16;
+        i += 1;
+    }
+
+    number
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`LAST_DAY`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +77,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > LAST_DAY {
             return None;
         }
         Some(Self(day))
@@ -45,11 +97,19 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if a puzzle of the event unlocked today, `None` otherwise.
+    /// Puzzles unlock daily from the 1st of december of [`EVENT_YEAR`] (any year, if not set) to the [`LAST_DAY`].
     pub fn today() -> Option<Self> {
+        Self::today_at(Utc::now())
+    }
+
+    /// Same as [`Day::today`], at the given time.
+    pub fn today_at(now: DateTime<Utc>) -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let today = now.with_timezone(&offset);
+        let is_event_year = EVENT_YEAR.is_none_or(|year| today.year() == i32::from(year));
+
+        if is_event_year && today.month() == 12 {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -94,18 +154,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {LAST_DAY}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the [`LAST_DAY`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the [`LAST_DAY`].
 pub struct AllDays {
     current: u8,
 }
@@ -121,10 +181,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > LAST_DAY {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,16 +194,139 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A sorted set of days, e.g. to run or download several days in one go.
+///
+/// # Parsing
+/// A set is parsed from a comma-separated list of days (`3,7,9`), ranges and `all`, which can be combined,
+/// e.g. `1..=3,10`. Like in Rust, `1..=5` includes the 5th day and `1..6` excludes the 6th.
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1..=3,10".parse().unwrap();
+/// assert_eq!(days.to_string(), "01, 02, 03, 10")
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<Day>);
+
+impl DaySet {
+    /// Every day of advent.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(BTreeSet::from([day]))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a DaySet {
+    type Item = Day;
+    type IntoIter = std::iter::Copied<std::collections::btree_set::Iter<'a, Day>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().copied()
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<String> = self.iter().map(|day| day.to_string()).collect();
+        f.write_str(&days.join(", "))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',').map(str::trim) {
+            if item.eq_ignore_ascii_case("all") {
+                days.extend(all_days());
+                continue;
+            }
+
+            let Some((start, end)) = item.split_once("..") else {
+                days.insert(parse_set_item(item)?);
+                continue;
+            };
+
+            let start = parse_set_item(start)?;
+            let last = match end.strip_prefix('=') {
+                Some(end) => parse_set_item(end)?.0,
+                // the exclusive end may be the day after the last one, like in `1..26`.
+                None if end.trim().parse() == Ok(LAST_DAY + 1) => LAST_DAY,
+                None => parse_set_item(end)?.0 - 1,
+            };
+            if last < start.0 {
+                return Err(DaySetFromStrError(format!("range `{item}` is empty")));
+            }
+
+            days.extend((start.0..=last).map(Day));
+        }
+
+        Ok(Self(days))
+    }
+}
+
+fn parse_set_item(s: &str) -> Result<Day, DaySetFromStrError> {
+    s.parse()
+        .map_err(|e| DaySetFromStrError(format!("invalid day `{s}`, {e}")))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}. Days are given as `3`, `3,7,9`, `1..=5`, `1..6` or `all`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::LAST_DAY,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and the last day of the event (see `AOC_DAYS`)"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +337,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, last_day, parse_const, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +370,44 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn configures_last_day() {
+        assert_eq!(last_day(None, None), 25);
+        assert_eq!(last_day(None, Some(2024)), 25);
+        assert_eq!(last_day(None, Some(2025)), 12);
+        assert_eq!(last_day(Some("7"), Some(2024)), 7);
+        assert_eq!(parse_const("2024"), 2024);
+    }
+
+    fn parse_days(s: &str) -> Vec<u8> {
+        s.parse::<DaySet>()
+            .unwrap()
+            .iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        assert_eq!(parse_days("7"), [7]);
+        assert_eq!(parse_days("9,3,7,3"), [3, 7, 9]);
+        assert_eq!(parse_days("1..4"), [1, 2, 3]);
+        assert_eq!(parse_days("1..=2, 23..25"), [1, 2, 23, 24]);
+        assert_eq!(parse_days("24..=25"), [24, 25]);
+        assert_eq!(parse_days("24..26"), [24, 25]);
+        assert_eq!(parse_days("all").len(), 25);
+        assert_eq!(parse_days("ALL,3").len(), 25);
+    }
+
+    #[test]
+    fn rejects_invalid_day_sets() {
+        for s in [
+            "", "0", "26", "1..=26", "1..27", "5..1", "3..3", "1,,2", "a..b", "1...3",
+        ] {
+            assert!(s.parse::<DaySet>().is_err(), "{s}");
+        }
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,221 @@
+/// Exports timings to formats that can be consumed outside of this project, e.g. by spreadsheets or dashboards.
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::{table_rows, Column, TableOptions};
+use crate::template::timings::{Timing, Timings};
+
+/// Keys of the JSON representation of a timing, in the order they are exported as CSV columns.
+static CSV_COLUMNS: [&str; 11] = [
+    "day",
+    "part_1_nanos",
+    "part_2_nanos",
+    "total_nanos",
+    "part_1_peak_bytes",
+    "part_2_peak_bytes",
+    "part_1_allocations",
+    "part_2_allocations",
+    "part_1_samples",
+    "part_2_samples",
+    "timed_out",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    JsonLines,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "jsonl" | "json-lines" | "jsonlines" => Ok(Format::JsonLines),
+            "md" | "markdown" => Ok(Format::Markdown),
+            other => Err(format!(
+                "unknown export format `{other}`, expected one of `csv`, `jsonl` or `markdown`."
+            )),
+        }
+    }
+}
+
+/// Write `timings` to `path` in the given format.
+pub fn write(timings: &Timings, format: Format, path: &Path) -> Result<(), Error> {
+    let content = match format {
+        Format::Csv => to_csv(timings),
+        Format::JsonLines => to_json_lines(timings)?,
+        Format::Markdown => to_markdown(timings),
+    };
+    fs::write(path, content)
+}
+
+/// The JSON representation of a timing, which always states whether the day timed out, so that its timings can't be
+/// mistaken for a complete measurement.
+fn to_json(timing: &Timing) -> HashMap<String, JsonValue> {
+    let mut map = match JsonValue::from(timing) {
+        JsonValue::Object(map) => map,
+        _ => HashMap::new(),
+    };
+    map.insert("timed_out".into(), JsonValue::Boolean(timing.timed_out));
+    map
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec![CSV_COLUMNS.join(",")];
+
+    for timing in &timings.data {
+        let map = to_json(timing);
+        let cells: Vec<String> = CSV_COLUMNS
+            .iter()
+            .map(|key| map.get(*key).map(csv_cell).unwrap_or_default())
+            .collect();
+        lines.push(cells.join(","));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn csv_cell(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) if s.contains([',', '"', '\n']) => {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        }
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Number(n) if n.fract() == 0.0 => format!("{n:.0}"),
+        JsonValue::Number(n) => n.to_string(),
+        JsonValue::Boolean(b) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn to_json_lines(timings: &Timings) -> Result<String, Error> {
+    let mut out = String::new();
+    for timing in &timings.data {
+        let line = JsonValue::Object(to_json(timing))
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn to_markdown(timings: &Timings) -> String {
+    let has_memory = timings.data.iter().any(Timing::has_memory);
+
+    let mut columns = vec![Column::Part1, Column::Part2, Column::Total];
+    if has_memory {
+        columns.push(Column::Memory);
+    }
+    columns.extend([Column::Samples, Column::TimedOut]);
+
+    let options = TableOptions {
+        columns: Some(columns),
+        part_totals: true,
+        ..TableOptions::default()
+    };
+
+    let total_millis = timings.total_millis();
+    let mut lines = vec!["# Benchmarks".to_string(), String::new()];
+    lines.extend(table_rows(timings, total_millis, &options));
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{to_csv, to_json_lines, to_markdown, Format};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    part_1_nanos: Some(10_000_000.0),
+                    part_2_nanos: Some(20_000_000.0),
+                    total_nanos: 3e+7,
+                    part_1_peak_bytes: Some(1024),
+                    part_1_allocations: Some(3),
+                    part_1_samples: Some(100),
+                    part_2_samples: Some(50),
+                    ..Timing::new(day!(1))
+                },
+                Timing {
+                    part_1_nanos: Some(1_500.0),
+                    total_nanos: 1500.0,
+                    timed_out: true,
+                    ..Timing::new(day!(2))
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("CSV"), Ok(Format::Csv));
+        assert_eq!(Format::from_str("jsonl"), Ok(Format::JsonLines));
+        assert_eq!(Format::from_str("markdown"), Ok(Format::Markdown));
+        assert!(Format::from_str("xlsx").is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let expected = [
+            "day,part_1_nanos,part_2_nanos,total_nanos,part_1_peak_bytes,part_2_peak_bytes,part_1_allocations,part_2_allocations,part_1_samples,part_2_samples,timed_out",
+            "01,10000000,20000000,30000000,1024,,3,,100,50,false",
+            "02,1500,,1500,,,,,,,true",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(to_csv(&get_mock_timings()), expected);
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let out = to_json_lines(&get_mock_timings()).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first = lines[0].parse::<JsonValue>().unwrap();
+        assert_eq!(first["timed_out"], JsonValue::Boolean(false));
+
+        let timing = Timing::try_from(&lines[1].parse::<JsonValue>().unwrap()).unwrap();
+        assert_eq!(timing.day, day!(2));
+        assert_eq!(timing.part_1_nanos, Some(1500.0));
+        assert!(timing.timed_out);
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let out = to_markdown(&get_mock_timings());
+        assert!(out.starts_with("# Benchmarks\n"));
+        assert!(out.contains("| Day | Part 1 | Part 2 | Total | Memory | Samples | Timed out |"));
+        assert!(out.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `30.00ms` | `1.0KiB` / `-` | `100` / `50` | no |"
+        ));
+        assert!(out.contains(
+            "| [Day 2](./src/bin/02.rs) | `1.5µs` | `timeout` | `0.00ms` | `-` / `-` | `-` / `-` | yes |"
+        ));
+        assert!(out.contains("**Total: 30.00ms**"));
+    }
+}
@@ -0,0 +1,288 @@
+/// Differential fuzzing between multiple implementations of a solution part.
+///
+/// A day registers a generator (producing random puzzle inputs for a seed and a size) and the variants
+/// that should agree for a part. The harness feeds generated inputs to every variant and, on disagreement,
+/// shrinks the input to a minimal failing case.
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::{env, process};
+
+use crate::template::generator::{arg_value, Generator};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// A named implementation of a solution part.
+pub type Variant<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// Settings for a fuzzing session, read from the command-line of the solution binary.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub runs: usize,
+    pub seed: u64,
+    pub max_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            runs: 200,
+            seed: 0,
+            max_size: 32,
+        }
+    }
+}
+
+impl Config {
+    /// Reads `--runs`, `--seed` and `--max-size` from the process arguments, falling back to defaults.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let default = Self::default();
+        Self {
+            runs: arg_value("--runs").unwrap_or(default.runs),
+            seed: arg_value("--seed").unwrap_or(default.seed),
+            max_size: arg_value("--max-size").unwrap_or(default.max_size).max(1),
+        }
+    }
+
+    fn size_for_run(&self, run: usize) -> usize {
+        1 + run * self.max_size / self.runs.max(1)
+    }
+}
+
+/// Returns whether the solution binary was invoked by `cargo fuzz-day`.
+#[must_use]
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--fuzz")
+}
+
+/// The result of a single variant: its answer, or the message it panicked with.
+pub type Outcome<T> = Result<Option<T>, String>;
+
+/// A (shrunk) input for which the variants of a part disagree.
+#[derive(Debug)]
+pub struct Mismatch<T> {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome<T>)>,
+}
+
+fn evaluate<T>(variants: &[Variant<T>], input: &str) -> Vec<(&'static str, Outcome<T>)> {
+    variants
+        .iter()
+        .map(|(name, func)| {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| func(input))).map_err(|e| {
+                e.downcast_ref::<&str>()
+                    .map(ToString::to_string)
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "panicked".into())
+            });
+            (*name, outcome)
+        })
+        .collect()
+}
+
+fn disagree<T: PartialEq>(outcomes: &[(&'static str, Outcome<T>)]) -> bool {
+    outcomes.windows(2).any(|w| w[0].1 != w[1].1)
+}
+
+/// Removes chunks of `separator`-delimited units from `input` as long as `fails` keeps returning `true`.
+fn shrink_units(input: &str, separator: &str, fails: impl Fn(&str) -> bool) -> String {
+    let trailing_newline = input.ends_with('\n');
+    let join = |units: &[&str]| {
+        let mut s = units.join(separator);
+        if trailing_newline && !s.is_empty() {
+            s.push('\n');
+        }
+        s
+    };
+
+    let mut units: Vec<&str> = input.trim_end_matches('\n').split(separator).collect();
+    let mut chunk = units.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed_any = false;
+
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<&str> = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .copied()
+                .collect();
+
+            if fails(&join(&candidate)) {
+                units = candidate;
+                removed_any = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+
+    join(&units)
+}
+
+/// Shrinks a failing case by first regenerating it at smaller sizes, then by removing paragraphs and lines.
+fn shrink<T: PartialEq>(
+    generator: Generator,
+    variants: &[Variant<T>],
+    seed: u64,
+    size: usize,
+) -> Mismatch<T> {
+    let fails = |input: &str| disagree(&evaluate(variants, input));
+
+    let size = (1..size)
+        .find(|&s| fails(&generator(seed, s)))
+        .unwrap_or(size);
+
+    let input = shrink_units(&generator(seed, size), "\n\n", fails);
+    let input = shrink_units(&input, "\n", fails);
+    let outcomes = evaluate(variants, &input);
+
+    Mismatch {
+        seed,
+        size,
+        input,
+        outcomes,
+    }
+}
+
+/// Feeds generated inputs to every variant and returns the first (shrunk) disagreement, if any.
+pub fn find_mismatch<T: PartialEq>(
+    generator: Generator,
+    variants: &[Variant<T>],
+    config: &Config,
+) -> Option<Mismatch<T>> {
+    (0..config.runs).find_map(|run| {
+        let seed = config.seed.wrapping_add(run as u64);
+        let size = config.size_for_run(run);
+
+        if disagree(&evaluate(variants, &generator(seed, size))) {
+            Some(shrink(generator, variants, seed, size))
+        } else {
+            None
+        }
+    })
+}
+
+/// Fuzz the variants of one part and report the outcome. Exits with a non-zero status on disagreement.
+pub fn run_part<T: PartialEq + Debug>(
+    day: Day,
+    part: u8,
+    generator: Generator,
+    variants: &[Variant<T>],
+) {
+    let config = Config::from_args();
+    let names = variants.iter().map(|v| v.0).collect::<Vec<_>>().join(", ");
+
+    // variants are expected to panic on some inputs, keep the output readable.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mismatch = find_mismatch(generator, variants, &config);
+    panic::set_hook(hook);
+
+    match mismatch {
+        None => println!(
+            "Part {part}: {ANSI_BOLD}ok{ANSI_RESET} {ANSI_ITALIC}({} runs, {names}){ANSI_RESET}",
+            config.runs
+        ),
+        Some(mismatch) => {
+            println!(
+                "Part {part}: {ANSI_BOLD}variants disagree{ANSI_RESET} {ANSI_ITALIC}(seed {}, size {}){ANSI_RESET}",
+                mismatch.seed, mismatch.size
+            );
+            for (name, outcome) in &mismatch.outcomes {
+                match outcome {
+                    Ok(result) => println!("  {name}: {result:?}"),
+                    Err(message) => println!("  {name}: panicked with \"{message}\""),
+                }
+            }
+            println!("Minimal input for day {day}:");
+            println!("{}", mismatch.input);
+            process::exit(1);
+        }
+    }
+}
+
+/// Called when `--fuzz` is passed to a solution that did not register any variants.
+pub fn unsupported(day: Day) {
+    eprintln!(
+        "Day {day} does not register a generator and variants. \
+        Use `solution!({}, generator: ..., variants: [...])` to enable fuzzing.",
+        day.into_inner()
+    );
+    process::exit(1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_mismatch, shrink_units, Config, Variant};
+    use crate::template::generator::Rng;
+
+    fn generate(seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(0, 100)))
+            .collect()
+    }
+
+    fn sum(input: &str) -> Option<usize> {
+        Some(input.lines().filter_map(|l| l.parse::<usize>().ok()).sum())
+    }
+
+    fn sum_capped(input: &str) -> Option<usize> {
+        Some(
+            input
+                .lines()
+                .filter_map(|l| l.parse::<usize>().ok())
+                .map(|x| x.min(90))
+                .sum(),
+        )
+    }
+
+    fn sum_panicking(input: &str) -> Option<usize> {
+        assert!(input.lines().count() < 5, "too many lines");
+        sum(input)
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let variants: [Variant<usize>; 2] = [("sum", sum), ("sum_again", sum)];
+        assert!(find_mismatch(generate, &variants, &Config::default()).is_none());
+    }
+
+    #[test]
+    fn shrinks_disagreement_to_single_line() {
+        let variants: [Variant<usize>; 2] = [("sum", sum), ("sum_capped", sum_capped)];
+        let mismatch = find_mismatch(generate, &variants, &Config::default()).unwrap();
+        assert_eq!(mismatch.input.lines().count(), 1);
+        assert!(mismatch.input.trim().parse::<usize>().unwrap() > 90);
+    }
+
+    #[test]
+    fn treats_panics_as_disagreement() {
+        let variants: [Variant<usize>; 2] = [("sum", sum), ("sum_panicking", sum_panicking)];
+        let mismatch = find_mismatch(generate, &variants, &Config::default()).unwrap();
+        assert_eq!(mismatch.input.lines().count(), 5);
+        assert!(mismatch.outcomes[1].1.is_err());
+    }
+
+    #[test]
+    fn shrink_keeps_trailing_newline() {
+        let shrunk = shrink_units("1\n2\n3\n", "\n", |s| s.contains('2'));
+        assert_eq!(shrunk, "2\n");
+    }
+
+    #[test]
+    fn shrinks_paragraphs() {
+        let shrunk = shrink_units("a\nb\n\nc\nd\n\ne\n", "\n\n", |s| s.contains('c'));
+        assert_eq!(shrunk, "c\nd\n");
+    }
+}
@@ -0,0 +1,93 @@
+//! Helpers for writing per-day puzzle input generators, as used by `cargo fuzz-day` and `cargo scale`.
+//!
+//! A generator is a plain function that produces a valid puzzle input for a seed and a size.
+//! Generators should be deterministic: the same seed and size always yield the same input.
+
+use std::{env, str::FromStr};
+
+/// Produces a valid puzzle input for a given seed and size.
+pub type Generator = fn(u64, usize) -> String;
+
+/// A small, deterministic pseudo-random number generator (splitmix64) for use in input generators.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `lo..hi`. Panics if the range is empty.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        assert!(lo < hi, "cannot sample from empty range {lo}..{hi}");
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.next_u64() % (hi - lo) as u64) as usize;
+        lo + offset
+    }
+
+    /// Returns `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.range(0, n) == 0
+    }
+
+    /// Returns a random element of `items`. Panics if `items` is empty.
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len())]
+    }
+}
+
+/// Builds a `rows` x `cols` character grid, one line per row, from a cell function.
+pub fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    let mut s = String::with_capacity(rows * (cols + 1));
+    for row in 0..rows {
+        for col in 0..cols {
+            s.push(cell(row, col));
+        }
+        s.push('\n');
+    }
+    s
+}
+
+/// Reads the value following `flag` from the arguments of the solution binary.
+pub(crate) fn arg_value<T: FromStr>(flag: &str) -> Option<T> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == flag)
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{grid, Rng};
+
+    #[test]
+    fn rng_is_deterministic() {
+        let a: Vec<_> = (0..5).map(|_| Rng::new(7).next_u64()).collect();
+        assert!(a.windows(2).all(|w| w[0] == w[1]));
+
+        let mut rng = Rng::new(7);
+        assert!((0..1000)
+            .map(|_| rng.range(3, 9))
+            .all(|x| (3..9).contains(&x)));
+    }
+
+    #[test]
+    fn builds_grids() {
+        assert_eq!(
+            grid(2, 3, |r, c| if r == c { '#' } else { '.' }),
+            "#..\n.#.\n"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+/// Append-only benchmark history, used to detect performance regressions between runs.
+///
+/// Every stored benchmark run is appended as one JSON object per line to `data/timings_history.jsonl`,
+/// together with the time of the run, the git commit and the machine it ran on.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::Day;
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out commit, suffixed with `-dirty` if the work tree had changes.
+    pub commit: Option<String>,
+    pub machine: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for a run that happened just now, on this machine, at the current commit.
+    pub fn now(timings: Timings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            commit: current_commit(),
+            machine: machine_id(),
+            timings,
+        }
+    }
+}
+
+/// Append an entry to the history file.
+pub fn append(entry: &HistoryEntry) -> Result<(), Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| Error::other(e.to_string()))?;
+    writeln!(file, "{line}")
+}
+
+/// Read all entries from the history file, oldest first. Lines that can not be parsed are skipped.
+pub fn read_all() -> Vec<HistoryEntry> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|s| parse_lines(&s))
+        .unwrap_or_default()
+}
+
+fn parse_lines(s: &str) -> Vec<HistoryEntry> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| JsonValue::from_str(l).ok())
+        .filter_map(|json| HistoryEntry::try_from(&json).ok())
+        .collect()
+}
+
+/// Returns the most recent timing of every part recorded on `machine`. Runs that only benched some days or parts
+/// keep the timings of the other parts from earlier runs.
+pub fn latest_for_machine(entries: &[HistoryEntry], machine: &str) -> Option<Timings> {
+    let mut latest: Vec<Timing> = vec![];
+
+    for timing in entries
+        .iter()
+        .filter(|e| e.machine == machine)
+        .flat_map(|e| &e.timings.data)
+    {
+        let index = match latest.iter().position(|t| t.day == timing.day) {
+            Some(index) => index,
+            None => {
+                latest.push(Timing::new(timing.day));
+                latest.len() - 1
+            }
+        };
+
+        let previous = &mut latest[index];
+        previous.part_1_nanos = timing.part_1_nanos.or(previous.part_1_nanos);
+        previous.part_2_nanos = timing.part_2_nanos.or(previous.part_2_nanos);
+    }
+
+    if latest.is_empty() {
+        return None;
+    }
+
+    latest.sort_unstable_by_key(|t| t.day);
+    Some(Timings { data: latest })
+}
+
+/// Identifies the machine a benchmark ran on: `AOC_MACHINE_ID` if set, the hostname otherwise.
+pub fn machine_id() -> String {
+    std::env::var("AOC_MACHINE_ID")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            Command::new("hostname")
+                .output()
+                .ok()
+                .and_then(|o| String::from_utf8(o.stdout).ok())
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change in execution time of a single part between two runs.
+#[derive(Clone, Debug)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+}
+
+impl PartComparison {
+    /// Relative change in percent, positive values mean the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        (self.after_nanos - self.before_nanos) / self.before_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compare every part that has a timing in both `before` and `after`.
+pub fn compare(before: &Timings, after: &Timings) -> Vec<PartComparison> {
+    after
+        .data
+        .iter()
+        .filter_map(|a| before.data.iter().find(|b| b.day == a.day).map(|b| (b, a)))
+        .flat_map(|(b, a)| {
+            [1, 2].into_iter().filter_map(move |part| {
+                Some(PartComparison {
+                    day: a.day,
+                    part,
+                    before_nanos: b.part_nanos(part)?,
+                    after_nanos: a.part_nanos(part)?,
+                })
+            })
+        })
+        .filter(|c| c.before_nanos > 0.0)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+
+        if let JsonValue::Object(timings) = JsonValue::from(value.timings.clone()) {
+            map.extend(timings);
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected entry.machine to be a string.")?;
+
+        let timings = Timings::try_from(value)?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            machine,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{compare, latest_for_machine, parse_lines, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timings(part_1: f64, part_2: Option<f64>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1_nanos: Some(part_1),
+                part_2_nanos: part_2,
+                ..Timing::new(day!(1))
+            }],
+        }
+    }
+
+    fn entry(machine: &str, part_1: f64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 1_733_000_000,
+            commit: Some("abc1234".into()),
+            machine: machine.into(),
+            timings: timings(part_1, None),
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let lines = [entry("a", 1e6), entry("b", 2e6)]
+            .iter()
+            .map(|e| JsonValue::from(e).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let entries = parse_lines(&lines);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, 1_733_000_000);
+        assert_eq!(entries[0].commit, Some("abc1234".into()));
+        assert_eq!(entries[1].machine, "b");
+        assert_eq!(entries[1].timings.data[0].part_1_nanos, Some(2e6));
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let entries = parse_lines("{}\nnot json\n\n");
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn finds_latest_timings_for_machine() {
+        let entries = [entry("a", 1e6), entry("b", 2e6), entry("a", 3e6)];
+        let latest = latest_for_machine(&entries, "a").unwrap();
+        assert_eq!(latest.data[0].part_1_nanos, Some(3e6));
+        assert!(latest_for_machine(&entries, "c").is_none());
+    }
+
+    #[test]
+    fn keeps_parts_missing_from_later_runs() {
+        let mut day_2 = entry("a", 4e6);
+        day_2.timings.data[0].day = day!(2);
+
+        let entries = [
+            HistoryEntry {
+                timings: timings(1e6, Some(2e6)),
+                ..entry("a", 0.0)
+            },
+            entry("a", 3e6),
+            day_2,
+        ];
+
+        let latest = latest_for_machine(&entries, "a").unwrap();
+        assert_eq!(latest.data.len(), 2);
+        assert_eq!(latest.data[0].part_1_nanos, Some(3e6));
+        assert_eq!(latest.data[0].part_2_nanos, Some(2e6));
+        assert_eq!(latest.data[1].day, day!(2));
+        assert_eq!(latest.data[1].part_1_nanos, Some(4e6));
+    }
+
+    #[test]
+    fn compares_parts_present_in_both_runs() {
+        let before = timings(1e6, None);
+        let after = timings(1.5e6, Some(1e6));
+
+        let comparisons = compare(&before, &after);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 1);
+        assert!((comparisons[0].change_percent() - 50.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(60.0));
+    }
+}
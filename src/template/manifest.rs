@@ -0,0 +1,326 @@
+/// Manifest of puzzle metadata, extracted from the descriptions downloaded to `data/puzzles`.
+///
+/// The metadata of all days is stored in `data/puzzles.json` and used to show puzzle titles in the readme,
+/// the output of `cargo status` and the doc comments of scaffolded solutions.
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static MANIFEST_FILE_PATH: &str = "./data/puzzles.json";
+
+/// Metadata of a single puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleMeta {
+    pub day: Day,
+    pub title: String,
+    pub url: Option<String>,
+    /// Seconds since the unix epoch at which the description was downloaded.
+    pub fetched_at: u64,
+}
+
+/// Metadata of all downloaded puzzles. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub data: Vec<PuzzleMeta>,
+}
+
+impl Manifest {
+    /// Rehydrate the manifest from a JSON file. If not present, returns an empty manifest.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(MANIFEST_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(|s| JsonValue::from_str(&s).map_err(|e| e.to_string()))
+            .and_then(|json| Manifest::try_from(&json))
+            .unwrap_or_default()
+    }
+
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(MANIFEST_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn title(&self, day: Day) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|m| m.day == day)
+            .map(|m| m.title.as_str())
+    }
+
+    pub fn titles(&self) -> HashMap<Day, String> {
+        self.data.iter().map(|m| (m.day, m.title.clone())).collect()
+    }
+
+    /// Insert or replace the metadata of a day.
+    pub fn insert(&mut self, meta: PuzzleMeta) {
+        self.data.retain(|m| m.day != meta.day);
+        self.data.push(meta);
+        self.data.sort_unstable_by_key(|m| m.day);
+    }
+}
+
+/// Extracts the title from the heading of a puzzle description, e.g. `## \-\-\- Day 1: Historian Hysteria \-\-\-`.
+pub fn title_from_markdown(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find(|l| l.contains("--- Day") || l.contains(r"\-\-\- Day"))?;
+    let title = heading.split_once(": ")?.1;
+    let title = title
+        .trim()
+        .trim_end_matches(['-', '\\', ' '])
+        .trim()
+        .to_string();
+
+    (!title.is_empty()).then_some(title)
+}
+
+pub fn puzzle_url(day: Day) -> Option<String> {
+    let year: u16 = std::env::var("AOC_YEAR").ok()?.parse().ok()?;
+    Some(format!(
+        "https://adventofcode.com/{year}/day/{}",
+        day.into_inner()
+    ))
+}
+
+/// Read the title of a day from its downloaded description, without adding it to the manifest.
+pub fn read_title(day: Day) -> Result<String, String> {
+    let path = format!("data/puzzles/{day}.md");
+    let description = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    title_from_markdown(&description).ok_or(format!("{path}: could not find puzzle title."))
+}
+
+/// Read the downloaded description of a day and add its metadata to the manifest.
+pub fn register(day: Day) -> Result<PuzzleMeta, String> {
+    let meta = PuzzleMeta {
+        day,
+        title: read_title(day)?,
+        url: puzzle_url(day),
+        fetched_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    };
+
+    let mut manifest = Manifest::read_from_file();
+    manifest.insert(meta.clone());
+    manifest.store_file().map_err(|e| e.to_string())?;
+
+    Ok(meta)
+}
+
+/// The heading of a puzzle, e.g. `Day 1: Historian Hysteria`, or `Day 1` if the title is not known.
+pub fn heading(day: Day, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("Day {}: {title}", day.into_inner()),
+        None => format!("Day {}", day.into_inner()),
+    }
+}
+
+/// The first line of the doc comment of scaffolded solutions, see the `%HEADING%` template placeholder.
+fn doc_heading(day: Day, title: Option<&str>) -> String {
+    format!("//! # {}", heading(day, title))
+}
+
+/// Add the title to the doc comment of a solution that was scaffolded before its description was downloaded.
+pub fn update_module_doc(day: Day, title: &str) -> Result<bool, Error> {
+    let path = format!("src/bin/{day}.rs");
+    let Ok(module) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+
+    let Some(updated) = add_title(&module, day, title) else {
+        return Ok(false);
+    };
+
+    fs::write(&path, updated)?;
+    Ok(true)
+}
+
+/// Replaces the untitled heading of a module, leaving the rest of it (including line endings) untouched.
+fn add_title(module: &str, day: Day, title: &str) -> Option<String> {
+    let untitled = doc_heading(day, None);
+    if !module.lines().any(|l| l == untitled) {
+        return None;
+    }
+
+    Some(module.replacen(&untitled, &doc_heading(day, Some(title)), 1))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|m| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("day".into(), JsonValue::String(m.day.to_string()));
+                map.insert("title".into(), JsonValue::String(m.title.clone()));
+                map.insert(
+                    "url".into(),
+                    m.url.clone().map_or(JsonValue::Null, JsonValue::String),
+                );
+                #[allow(clippy::cast_precision_loss)]
+                map.insert("fetched_at".into(), JsonValue::Number(m.fetched_at as f64));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PuzzleMeta {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected puzzle metadata to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected puzzle.day to be a Day struct.")?;
+
+        let title = json
+            .get("title")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected puzzle.title to be a string.")?;
+
+        let url = json.get("url").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .unwrap_or_default();
+
+        Ok(PuzzleMeta {
+            day,
+            title,
+            url,
+            fetched_at,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Manifest {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let data = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Manifest {
+            data: data
+                .iter()
+                .map(PuzzleMeta::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{add_title, doc_heading, title_from_markdown, Manifest, PuzzleMeta};
+    use crate::day;
+
+    fn meta(day: crate::template::Day, title: &str) -> PuzzleMeta {
+        PuzzleMeta {
+            day,
+            title: title.into(),
+            url: None,
+            fetched_at: 1_733_000_000,
+        }
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(
+            title_from_markdown("## \\-\\-\\- Day 1: Historian Hysteria \\-\\-\\-\n\nThe Chief..."),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(
+            title_from_markdown("--- Day 12: Garden Groups ---"),
+            Some("Garden Groups".into())
+        );
+        assert_eq!(title_from_markdown("# nothing here"), None);
+    }
+
+    #[test]
+    fn replaces_existing_entries() {
+        let mut manifest = Manifest::default();
+        manifest.insert(meta(day!(2), "Red-Nosed Reports"));
+        manifest.insert(meta(day!(1), "Historian"));
+        manifest.insert(meta(day!(1), "Historian Hysteria"));
+
+        assert_eq!(manifest.data.len(), 2);
+        assert_eq!(manifest.data[0].day, day!(1));
+        assert_eq!(manifest.title(day!(1)), Some("Historian Hysteria"));
+        assert_eq!(manifest.title(day!(3)), None);
+    }
+
+    #[test]
+    fn roundtrips_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.insert(meta(day!(1), "Historian Hysteria"));
+
+        let parsed = Manifest::try_from(&JsonValue::from(&manifest)).unwrap();
+        assert_eq!(parsed.data, manifest.data);
+    }
+
+    #[test]
+    fn formats_doc_headings() {
+        assert_eq!(doc_heading(day!(1), None), "//! # Day 1");
+        assert_eq!(
+            doc_heading(day!(1), Some("Historian Hysteria")),
+            "//! # Day 1: Historian Hysteria"
+        );
+    }
+
+    #[test]
+    fn adds_titles_to_module_docs() {
+        assert_eq!(
+            add_title(
+                "//! # Day 1\r\n//!\r\nfn main() {}",
+                day!(1),
+                "Historian Hysteria"
+            ),
+            Some("//! # Day 1: Historian Hysteria\r\n//!\r\nfn main() {}".into())
+        );
+        assert_eq!(
+            add_title("//! # Day 1\n", day!(1), "Historian Hysteria"),
+            Some("//! # Day 1: Historian Hysteria\n".into())
+        );
+        assert_eq!(
+            add_title("//! # Day 1: Historian Hysteria\n", day!(1), "Other"),
+            None
+        );
+        assert_eq!(
+            add_title("fn main() {}", day!(1), "Historian Hysteria"),
+            None
+        );
+    }
+}
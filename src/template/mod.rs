@@ -1,19 +1,42 @@
 use std::{env, fs};
 
+#[cfg(feature = "dhat-heap")]
+pub mod allocations;
 pub mod aoc_cli;
 pub mod commands;
+pub mod export;
+pub mod fuzz;
+pub mod generator;
+pub mod memory;
+#[cfg(feature = "cpu-profile")]
+pub mod profile;
 pub mod runner;
+pub mod scale;
+pub mod trace;
+pub mod viz;
 
 pub use day::*;
 
+mod baseline;
+mod calendar;
 mod day;
+mod history;
+mod manifest;
+mod progress;
 mod readme_benchmarks;
 mod run_multi;
+mod templates;
 mod timings;
+#[cfg(feature = "today")]
+pub mod unlock;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
@@ -39,19 +62,48 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, an input `generator` can be registered to enable `cargo scale`, optionally followed
+/// by the `variants` of each part that should agree to enable `cargo fuzz-day`:
+///
+/// ```ignore
+/// advent_of_code::solution!(9, generator: generate);
+/// advent_of_code::solution!(13, generator: generate, variants: [1 => [part_one, naive_part_one]]);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, None, { $crate::template::fuzz::unsupported(DAY) }, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, None, { $crate::template::fuzz::unsupported(DAY) }, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, None, { $crate::template::fuzz::unsupported(DAY) }, [part_two, 2]);
     };
+    ($day:expr, generator: $generator:path $(,)?) => {
+        $crate::solution!(@impl $day, Some($generator), { $crate::template::fuzz::unsupported(DAY) }, [part_one, 1] [part_two, 2]);
+    };
+    (
+        $day:expr,
+        generator: $generator:path,
+        variants: [$( $variant_part:literal => [$( $variant:path ),+ $(,)?] ),* $(,)?] $(,)?
+    ) => {
+        $crate::solution!(@impl $day, Some($generator), { fuzz() }, [part_one, 1] [part_two, 2]);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        fn fuzz() {
+            $(
+                $crate::template::fuzz::run_part(
+                    DAY,
+                    $variant_part,
+                    $generator,
+                    &[$( (stringify!($variant), $variant) ),+],
+                );
+            )*
+        }
+    };
+
+    (@impl $day:expr, $generator:expr, $fuzz:block, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +111,46 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "memory", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
+
+            if $crate::template::fuzz::is_requested() {
+                $fuzz;
+                return;
+            }
+
+            if $crate::template::scale::is_requested() {
+                let generator: Option<$crate::template::generator::Generator> = $generator;
+                $( $crate::template::scale::run_part(DAY, $part, generator, $func); )*
+                return;
+            }
+
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let result = (|| -> Result<(), $crate::template::commands::Error> {
+                // parts without an answer do not keep the remaining parts from running.
+                let mut unanswered = vec![];
+                $(
+                    match run_part($func, &input, DAY, $part) {
+                        Err($crate::template::commands::Error::NoAnswer(parts)) => unanswered.extend(parts),
+                        result => result?,
+                    }
+                )*
+
+                if unanswered.is_empty() {
+                    Ok(())
+                } else {
+                    Err($crate::template::commands::Error::NoAnswer(unanswered))
+                }
+            })();
+
+            if let Err(err) = result {
+                eprintln!("Error: {err}");
+                std::process::exit(err.exit_code());
+            }
         }
     };
 }
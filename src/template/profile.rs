@@ -0,0 +1,57 @@
+/// Samples a solution with an in-process CPU profiler and writes a flamegraph, for `cargo solve --profile cpu`.
+///
+/// A single run of a part is usually over before the profiler has taken meaningful samples, so the part is run
+/// repeatedly until [`MIN_DURATION`] has passed.
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use pprof::ProfilerGuardBuilder;
+
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Minimum time a part is sampled for.
+const MIN_DURATION: Duration = Duration::from_secs(5);
+
+/// Samples per second. Not a round number, to avoid sampling in lockstep with periodic work.
+const FREQUENCY: i32 = 997;
+
+/// Runs a part until [`MIN_DURATION`] has passed and writes its flamegraph to `profiles/<day>-<part>.svg`.
+pub fn run_part<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day, part: u8) {
+    match profile(func, input, day, part) {
+        Ok((path, runs)) => println!(
+            "Part {part}: {ANSI_BOLD}{}{ANSI_RESET} ({runs} runs)",
+            path.display()
+        ),
+        Err(e) => eprintln!("Part {part}: failed to profile: {e}"),
+    }
+}
+
+fn profile<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Result<(PathBuf, u64), Box<dyn std::error::Error>> {
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let timer = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || timer.elapsed() < MIN_DURATION {
+        black_box(func(black_box(input.clone())));
+        runs += 1;
+    }
+
+    let report = guard.report().build()?;
+
+    let dir = PathBuf::from("profiles");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{day}-{part}.svg"));
+    report.flamegraph(File::create(&path)?)?;
+
+    Ok((path, runs))
+}
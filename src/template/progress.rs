@@ -0,0 +1,508 @@
+/// Local record of solved puzzles, used to track ⭐️ progress without access to the Advent of Code website.
+///
+/// Every answer submitted through `cargo solve --submit` is recorded in `data/progress.json`,
+/// together with the number of attempts and the time the part was solved.
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::SubmissionOutcome;
+use crate::template::manifest::{self, Manifest};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET, LAST_DAY};
+
+static PROGRESS_FILE_PATH: &str = "./data/progress.json";
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Progress of a single part of a puzzle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PartProgress {
+    pub solved: bool,
+    /// Seconds since the unix epoch at which the correct answer was submitted, if known.
+    pub solved_at: Option<u64>,
+    /// Number of answers submitted for this part, including the correct one.
+    pub attempts: u32,
+}
+
+impl PartProgress {
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+}
+
+/// Progress of both parts of a day.
+#[derive(Clone, Debug)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: PartProgress,
+    pub part_2: PartProgress,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> u8 {
+        u8::from(self.part_1.is_solved()) + u8::from(self.part_2.is_solved())
+    }
+}
+
+/// Progress of all days. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    pub data: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Rehydrate progress from a JSON file. If not present, returns empty progress.
+    ///
+    /// A file that can not be parsed is an error, so that it is not overwritten by the next recorded submission.
+    pub fn read_from_file() -> Result<Self, Error> {
+        let s = match fs::read_to_string(PROGRESS_FILE_PATH) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Progress::default()),
+            Err(e) => return Err(e),
+        };
+
+        JsonValue::from_str(&s)
+            .map_err(|e| e.to_string())
+            .and_then(|json| Progress::try_from(&json))
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{PROGRESS_FILE_PATH}: {e}")))
+    }
+
+    /// Dehydrate progress to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(PROGRESS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn day(&self, day: Day) -> Option<&DayProgress> {
+        self.data.iter().find(|d| d.day == day)
+    }
+
+    pub fn stars(&self, day: Day) -> u8 {
+        self.day(day).map_or(0, DayProgress::stars)
+    }
+
+    pub fn total_stars(&self) -> u32 {
+        self.data.iter().map(|d| u32::from(d.stars())).sum()
+    }
+
+    /// Record the outcome of submitting an answer for a part. Returns whether the progress changed.
+    pub fn record(&mut self, day: Day, part: u8, outcome: SubmissionOutcome, now: u64) -> bool {
+        if outcome == SubmissionOutcome::Unknown {
+            return false;
+        }
+
+        let index = match self.data.iter().position(|d| d.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayProgress {
+                    day,
+                    part_1: PartProgress::default(),
+                    part_2: PartProgress::default(),
+                });
+                self.data.sort_unstable_by_key(|d| d.day);
+                self.data.iter().position(|d| d.day == day).unwrap()
+            }
+        };
+
+        let progress = match part {
+            1 => &mut self.data[index].part_1,
+            2 => &mut self.data[index].part_2,
+            _ => return false,
+        };
+
+        match outcome {
+            SubmissionOutcome::Correct => {
+                progress.attempts += 1;
+                if !progress.solved {
+                    progress.solved = true;
+                    progress.solved_at = Some(now);
+                }
+            }
+            SubmissionOutcome::Incorrect => progress.attempts += 1,
+            // the part was solved before it was tracked locally, the time it was solved is unknown.
+            SubmissionOutcome::AlreadySolved => progress.solved = true,
+            SubmissionOutcome::Unknown => {}
+        }
+
+        true
+    }
+}
+
+/// Record the outcome of a submission in the progress file.
+pub fn record_submission(day: Day, part: u8, outcome: SubmissionOutcome) -> Result<(), Error> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut progress = Progress::read_from_file()?;
+    if progress.record(day, part, outcome, now) {
+        progress.store_file()?;
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn star_cell(stars: u8) -> &'static str {
+    match stars {
+        2 => "★★",
+        1 => "★☆",
+        _ => "☆☆",
+    }
+}
+
+/// Renders the stars of all days as a calendar with five days per row.
+pub fn calendar(progress: &Progress) -> String {
+    let days: Vec<Day> = all_days().collect();
+    let mut lines = vec![];
+
+    for week in days.chunks(5) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|day| {
+                let stars = progress.stars(*day);
+                let cell = format!("{:>2} {}", day.into_inner(), star_cell(stars));
+                if stars == 2 {
+                    format!("{ANSI_BOLD}{cell}{ANSI_RESET}")
+                } else {
+                    cell
+                }
+            })
+            .collect();
+        lines.push(cells.join("  "));
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the progress table in the format of the `advent-readme-stars` action, so both can be used interchangeably.
+fn construct_table(progress: &Progress, year: Option<u16>) -> Vec<String> {
+    let mut lines = vec![
+        year.map_or_else(|| "## Results".into(), |year| format!("## {year} Results")),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in &progress.data {
+        if day.stars() == 0 {
+            continue;
+        }
+
+        let name = format!("Day {}", day.day.into_inner());
+        let link = year.map_or(name.clone(), |year| {
+            format!(
+                "[{name}](https://adventofcode.com/{year}/day/{})",
+                day.day.into_inner()
+            )
+        });
+
+        let star = |p: &PartProgress| if p.is_solved() { "⭐" } else { " " };
+        lines.push(format!(
+            "| {link} | {} | {} |",
+            star(&day.part_1),
+            star(&day.part_2)
+        ));
+    }
+
+    lines
+}
+
+/// Replace the table following the marker, including its heading and trailing blank lines, with the current progress.
+fn update_content(s: &mut String, progress: &Progress, year: Option<u16>) -> Result<(), String> {
+    let start = s
+        .find(MARKER)
+        .ok_or("Could not find progress marker in README.")?
+        + MARKER.len();
+
+    // keep the line break after the marker.
+    let first_line_end = s[start..].find('\n').map_or(s.len(), |i| start + i + 1);
+
+    let mut end = first_line_end;
+    let mut in_table = false;
+    for line in s[first_line_end..].split_inclusive('\n') {
+        let trimmed = line.trim();
+        let is_row = trimmed.starts_with('|');
+        let is_heading = trimmed.starts_with("## ") && trimmed.ends_with("Results");
+
+        if is_row {
+            in_table = true;
+        } else if !(trimmed.is_empty() || (is_heading && !in_table)) {
+            break;
+        }
+        end += line.len();
+    }
+
+    let mut table = construct_table(progress, year).join("\n");
+    table.push_str("\n\n");
+    s.replace_range(first_line_end..end, &table);
+    Ok(())
+}
+
+pub fn update_readme(progress: &Progress) -> Result<(), String> {
+    let path = "README.md";
+    let mut readme = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let year = std::env::var("AOC_YEAR").ok().and_then(|y| y.parse().ok());
+    update_content(&mut readme, progress, year)?;
+    fs::write(path, readme).map_err(|e| e.to_string())
+}
+
+pub fn print_status(progress: &Progress, manifest: &Manifest) {
+    println!("{ANSI_BOLD}Progress{ANSI_RESET}");
+    println!();
+    println!("{}", calendar(progress));
+    println!();
+
+    for day in progress.data.iter().filter(|d| d.stars() > 0) {
+        println!(
+            "{} {}",
+            star_cell(day.stars()),
+            manifest::heading(day.day, manifest.title(day.day))
+        );
+    }
+    if progress.total_stars() > 0 {
+        println!();
+    }
+
+    println!(
+        "Stars: {}/{}",
+        progress.total_stars(),
+        2 * u32::from(LAST_DAY)
+    );
+
+    let attempts: u32 = progress
+        .data
+        .iter()
+        .map(|d| d.part_1.attempts + d.part_2.attempts)
+        .sum();
+    if attempts > 0 {
+        println!("Answers submitted: {attempts}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartProgress> for JsonValue {
+    fn from(value: &PartProgress) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("solved".into(), JsonValue::Boolean(value.solved));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "solved_at".into(),
+            value
+                .solved_at
+                .map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+        );
+        map.insert(
+            "attempts".into(),
+            JsonValue::Number(f64::from(value.attempts)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Progress> for JsonValue {
+    fn from(value: &Progress) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|d| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("day".into(), JsonValue::String(d.day.to_string()));
+                map.insert("part_1".into(), JsonValue::from(&d.part_1));
+                map.insert("part_2".into(), JsonValue::from(&d.part_2));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartProgress {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part progress to be a JSON object.")?;
+
+        let solved = *json
+            .get("solved")
+            .and_then(|v| v.get::<bool>())
+            .ok_or("Expected progress.solved to be a boolean.")?;
+
+        let solved_at = json
+            .get("solved_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        let attempts = json
+            .get("attempts")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u32)
+            .ok_or("Expected progress.attempts to be a number.")?;
+
+        Ok(PartProgress {
+            solved,
+            solved_at,
+            attempts,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Progress {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let data = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = data
+            .iter()
+            .map(|value| {
+                let json = value
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected day progress to be a JSON object.")?;
+
+                let day = json
+                    .get("day")
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|day| Day::from_str(day).ok())
+                    .ok_or("Expected progress.day to be a Day struct.")?;
+
+                let part = |key: &str| {
+                    json.get(key)
+                        .map_or(Ok(PartProgress::default()), PartProgress::try_from)
+                };
+
+                Ok(DayProgress {
+                    day,
+                    part_1: part("part_1")?,
+                    part_2: part("part_2")?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Progress { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{calendar, update_content, Progress, MARKER};
+    use crate::template::LAST_DAY;
+    use crate::{day, template::aoc_cli::SubmissionOutcome};
+
+    fn get_mock_progress() -> Progress {
+        let mut progress = Progress::default();
+        progress.record(day!(2), 1, SubmissionOutcome::Incorrect, 10);
+        progress.record(day!(2), 1, SubmissionOutcome::Correct, 20);
+        progress.record(day!(2), 2, SubmissionOutcome::Correct, 30);
+        progress.record(day!(1), 1, SubmissionOutcome::Correct, 5);
+        progress
+    }
+
+    #[test]
+    fn records_submissions() {
+        let progress = get_mock_progress();
+        assert_eq!(progress.data[0].day, day!(1));
+        assert_eq!(progress.stars(day!(1)), 1);
+        assert_eq!(progress.stars(day!(2)), 2);
+        assert_eq!(progress.stars(day!(3)), 0);
+        assert_eq!(progress.total_stars(), 3);
+
+        let part_1 = progress.day(day!(2)).unwrap().part_1;
+        assert_eq!(part_1.attempts, 2);
+        assert_eq!(part_1.solved_at, Some(20));
+    }
+
+    #[test]
+    fn keeps_first_solve_time() {
+        let mut progress = get_mock_progress();
+        progress.record(day!(1), 1, SubmissionOutcome::Correct, 100);
+        assert_eq!(progress.day(day!(1)).unwrap().part_1.solved_at, Some(5));
+        assert!(!progress.record(day!(1), 2, SubmissionOutcome::Unknown, 100));
+    }
+
+    #[test]
+    fn records_parts_solved_before_tracking() {
+        let mut progress = get_mock_progress();
+        progress.record(day!(3), 1, SubmissionOutcome::AlreadySolved, 100);
+
+        let part_1 = progress.day(day!(3)).unwrap().part_1;
+        assert!(part_1.is_solved());
+        assert_eq!(part_1.solved_at, None);
+
+        let json = JsonValue::from(&progress);
+        let part_1 = Progress::try_from(&json)
+            .unwrap()
+            .day(day!(3))
+            .unwrap()
+            .part_1;
+        assert!(part_1.is_solved());
+        assert_eq!(part_1.solved_at, None);
+    }
+
+    #[test]
+    fn roundtrips_progress() {
+        let json = JsonValue::from(&get_mock_progress());
+        let progress = Progress::try_from(&json).unwrap();
+        assert_eq!(progress.total_stars(), 3);
+        assert_eq!(progress.day(day!(2)).unwrap().part_1.attempts, 2);
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let grid = calendar(&Progress::default());
+        assert_eq!(grid.lines().count(), usize::from(LAST_DAY).div_ceil(5));
+        assert!(grid.starts_with(" 1 ☆☆   2 ☆☆"));
+    }
+
+    #[test]
+    fn updates_readme_table() {
+        let mut s = format!("# readme\n\n{MARKER}\n\n## Other\n");
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+
+        let expected = [
+            "# readme",
+            "",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ |   |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ | ⭐ |",
+            "",
+            "## Other",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        // updating twice replaces the existing table.
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        assert_eq!(s, expected);
+    }
+}
@@ -1,13 +1,17 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, fs, io};
 
-use crate::template::timings::Timings;
+use crate::template::manifest::{self, Manifest};
+use crate::template::timings::{Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CHART_PATH: &str = ".assets/benchmarks.svg";
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -20,6 +24,15 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(message) => write!(f, "{message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -52,76 +65,398 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/* -------------------------------------------------------------------------- */
+
+/// A column of the benchmark table, next to the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Part1,
+    Part2,
+    Total,
+    Memory,
+    Samples,
+    TimedOut,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "part_1" | "part1" | "1" => Ok(Column::Part1),
+            "part_2" | "part2" | "2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "memory" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            "timed_out" => Ok(Column::TimedOut),
+            other => Err(format!("unknown column `{other}`.")),
+        }
+    }
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Memory => "Memory",
+            Column::Samples => "Samples",
+            Column::TimedOut => "Timed out",
+        }
+    }
+
+    fn cell(self, timing: &Timing) -> String {
+        fn code(s: Option<String>) -> String {
+            format!("`{}`", s.unwrap_or_else(|| "-".into()))
+        }
+
+        // parts that did not finish before the solution was killed.
+        let part = |part: u8| {
+            timing
+                .format_part(part)
+                .or_else(|| timing.timed_out.then(|| "timeout".into()))
+        };
+
+        match self {
+            Column::Part1 => code(part(1)),
+            Column::Part2 => code(part(2)),
+            Column::Total => code(Some(format_millis(timing.total_nanos / 1_000_000_f64))),
+            Column::Memory => format!(
+                "{} / {}",
+                code(timing.format_peak_bytes(1)),
+                code(timing.format_peak_bytes(2))
+            ),
+            Column::Samples => format!(
+                "{} / {}",
+                code(timing.part_1_samples.map(|s| s.to_string())),
+                code(timing.part_2_samples.map(|s| s.to_string()))
+            ),
+            Column::TimedOut => if timing.timed_out { "yes" } else { "no" }.into(),
+        }
+    }
+
+    fn total_cell(self, timings: &Timings, total_millis: f64) -> String {
+        match self {
+            Column::Part1 => format!("**{}**", format_millis(timings.part_total_millis(1))),
+            Column::Part2 => format!("**{}**", format_millis(timings.part_total_millis(2))),
+            Column::Total => format!("**{}**", format_millis(total_millis)),
+            Column::Memory | Column::Samples | Column::TimedOut => String::new(),
+        }
+    }
+}
+
+/// Order of the rows in the benchmark table. Timings are sorted slowest first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortBy {
+    #[default]
+    Day,
+    Total,
+    Part1,
+    Part2,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(SortBy::Day),
+            "total" => Ok(SortBy::Total),
+            "part_1" | "part1" => Ok(SortBy::Part1),
+            "part_2" | "part2" => Ok(SortBy::Part2),
+            other => Err(format!("unknown sort order `{other}`.")),
+        }
+    }
+}
+
+impl SortBy {
+    fn key(self, timing: &Timing) -> f64 {
+        match self {
+            SortBy::Day => f64::from(timing.day.into_inner()),
+            SortBy::Total => -timing.total_nanos,
+            SortBy::Part1 | SortBy::Part2 => {
+                let part = if self == SortBy::Part1 { 1 } else { 2 };
+                timing
+                    .part_nanos(part)
+                    .map_or(f64::INFINITY, |nanos| -nanos)
+            }
+        }
+    }
+}
+
+/// Layout of the benchmark table, read from the `AOC_README_*` environment variables.
+/// These can be set in the `[env]` section of `.cargo/config.toml`.
+#[derive(Clone, Debug, Default)]
+pub struct TableOptions {
+    /// Columns shown next to the day (`AOC_README_COLUMNS`, comma-separated).
+    /// Defaults to both parts, plus memory usage if any day was measured with it.
+    pub columns: Option<Vec<Column>>,
+    /// Row order (`AOC_README_SORT`).
+    pub sort_by: SortBy,
+    /// Number of slowest days that are shown in bold (`AOC_README_HIGHLIGHT`).
+    pub highlight_slowest: usize,
+    /// Add a row with the sum of every part (`AOC_README_TOTALS`).
+    pub part_totals: bool,
+    /// Generate a bar chart at `.assets/benchmarks.svg` and link it below the table (`AOC_README_CHART`).
+    pub chart: bool,
+    /// Puzzle titles shown next to the day, read from the puzzle manifest.
+    pub titles: HashMap<Day, String>,
+}
+
+impl TableOptions {
+    pub fn from_env() -> Self {
+        fn flag(name: &str) -> bool {
+            env::var(name).is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"))
+        }
+
+        let columns = env::var("AOC_README_COLUMNS").ok().map(|v| {
+            v.split(',')
+                .filter(|c| !c.trim().is_empty())
+                .filter_map(|c| {
+                    c.parse()
+                        .map_err(|e| eprintln!("AOC_README_COLUMNS: skipping `{c}`, {e}"))
+                        .ok()
+                })
+                .collect()
+        });
+
+        let sort_by = env::var("AOC_README_SORT")
+            .ok()
+            .and_then(|v| {
+                v.parse()
+                    .map_err(|e| eprintln!("AOC_README_SORT: {e}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        let highlight_slowest = env::var("AOC_README_HIGHLIGHT")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or_default();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+        Self {
+            columns,
+            sort_by,
+            highlight_slowest,
+            part_totals: flag("AOC_README_TOTALS"),
+            chart: flag("AOC_README_CHART"),
+            titles: HashMap::new(),
+        }
+    }
+}
 
-    for timing in timings.data {
+fn format_millis(millis: f64) -> String {
+    format!("{millis:.2}ms")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the rows of the benchmark table, without the surrounding markers and header.
+pub(crate) fn table_rows(
+    timings: &Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Vec<String> {
+    let columns = options.columns.clone().unwrap_or_else(|| {
+        // only show the memory column if at least one day was measured with the `memory` feature.
+        let has_memory = timings.data.iter().any(Timing::has_memory);
+
+        let mut columns = vec![Column::Part1, Column::Part2];
+        if has_memory {
+            columns.push(Column::Memory);
+        }
+        columns
+    });
+
+    let mut slowest: Vec<&Timing> = timings.data.iter().collect();
+    slowest.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    let highlighted: Vec<Day> = slowest
+        .iter()
+        .take(options.highlight_slowest)
+        .map(|t| t.day)
+        .collect();
+
+    let mut rows: Vec<&Timing> = timings.data.iter().collect();
+    rows.sort_by(|a, b| {
+        options
+            .sort_by
+            .key(a)
+            .total_cmp(&options.sort_by.key(b))
+            .then(a.day.cmp(&b.day))
+    });
+
+    let mut header_row = String::from("| Day |");
+    let mut separator_row = String::from("| :---: |");
+    for column in &columns {
+        header_row.push_str(&format!(" {} |", column.header()));
+        separator_row.push_str(if *column == Column::Part2 {
+            " :---:  |"
+        } else {
+            " :---: |"
+        });
+    }
+
+    let mut lines = vec![header_row, separator_row];
+
+    for timing in rows {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let heading = manifest::heading(
+            timing.day,
+            options.titles.get(&timing.day).map(String::as_str),
+        );
+        let mut day_cell = format!("[{heading}]({path})");
+        if highlighted.contains(&timing.day) {
+            day_cell = format!("**{day_cell}**");
+        }
+
+        let mut line = format!("| {day_cell} |");
+        for column in &columns {
+            line.push_str(&format!(" {} |", column.cell(timing)));
+        }
+        lines.push(line);
     }
 
+    if options.part_totals {
+        let mut line = String::from("| **Total** |");
+        for column in &columns {
+            line.push_str(&format!(" {} |", column.total_cell(timings, total_millis)));
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    lines.extend(table_rows(&timings, total_millis, options));
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if options.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart](./{CHART_PATH})"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+/// Renders the total time of every day as a horizontal bar chart on a logarithmic scale.
+fn construct_chart(timings: &Timings) -> String {
+    const LABEL_WIDTH: f64 = 70.0;
+    const BAR_WIDTH: f64 = 420.0;
+    const ROW_HEIGHT: f64 = 24.0;
+
+    // bars start at 1µs, so that very fast days are still visible.
+    let floor = 1_000_f64.ln();
+    let max = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(10_000_f64, f64::max)
+        .ln();
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * timings.data.len() as f64 + 8.0;
+    let width = LABEL_WIDTH + BAR_WIDTH + 90.0;
+
+    let mut svg = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">"
+    )];
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW_HEIGHT * i as f64 + 4.0;
+        let scaled = (timing.total_nanos.max(1.0).ln() - floor) / (max - floor);
+        let bar = (scaled.clamp(0.0, 1.0) * BAR_WIDTH).max(1.0);
+
+        svg.push(format!(
+            "  <text x=\"0\" y=\"{:.1}\">Day {}</text>",
+            y + 15.0,
+            timing.day.into_inner()
+        ));
+        svg.push(format!(
+            "  <rect x=\"{LABEL_WIDTH}\" y=\"{y:.1}\" width=\"{bar:.1}\" height=\"{:.1}\" fill=\"#e2a53c\"/>",
+            ROW_HEIGHT - 6.0
+        ));
+        svg.push(format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            LABEL_WIDTH + bar + 6.0,
+            y + 15.0,
+            format_millis(timing.total_nanos / 1_000_000_f64)
+        ));
+    }
+
+    svg.push("</svg>".into());
+    svg.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let options = TableOptions {
+        titles: Manifest::read_from_file().titles(),
+        ..TableOptions::from_env()
+    };
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    if options.chart {
+        fs::write(CHART_PATH, construct_chart(&timings))?;
+    }
+
+    update_content(&mut readme, timings, total_millis, &options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
+    use super::{construct_chart, update_content, Column, SortBy, TableOptions, MARKER};
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10_000_000.0),
+                    part_2_nanos: Some(20_000_000.0),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30_000_000.0),
+                    part_2_nanos: Some(40_000_000.0),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1_nanos: Some(40_000_000.0),
+                    part_2_nanos: Some(50_000_000.0),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,28 +466,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +495,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +504,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +515,99 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_peak_bytes = Some(1024);
+        timings.data[0].part_2_peak_bytes = Some(2048);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory |"));
+        assert!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `1.0KiB` / `2.0KiB` |")
+        );
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `-` / `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1_samples = Some(100);
+        timings.data[1].part_2_samples = Some(10);
+
+        let options = TableOptions {
+            columns: Some(vec![Column::Total, Column::Samples]),
+            sort_by: SortBy::Total,
+            highlight_slowest: 1,
+            part_totals: true,
+            chart: true,
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Total | Samples |",
+            "| :---: | :---: | :---: |",
+            "| **[Day 4](./src/bin/04.rs)** | `90000.00ms` | `-` / `-` |",
+            "| [Day 2](./src/bin/02.rs) | `70000.00ms` | `100` / `10` |",
+            "| [Day 1](./src/bin/01.rs) | `30000.00ms` | `-` / `-` |",
+            "| **Total** | **190.00ms** |  |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_titles() {
+        let options = TableOptions {
+            titles: [(day!(2), "Red-Nosed Reports".to_string())].into(),
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) |"));
+        assert!(s.contains("| [Day 2: Red-Nosed Reports](./src/bin/02.rs) |"));
+    }
+
+    #[test]
+    fn format_part_totals() {
+        let options = TableOptions {
+            part_totals: true,
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(s.contains("| **Total** | **80.00ms** | **110.00ms** |"));
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!("part_1".parse::<Column>(), Ok(Column::Part1));
+        assert_eq!(" memory".parse::<Column>(), Ok(Column::Memory));
+        assert!("median".parse::<Column>().is_err());
+        assert_eq!("total".parse::<SortBy>(), Ok(SortBy::Total));
+    }
+
+    #[test]
+    fn draws_one_bar_per_day() {
+        let svg = construct_chart(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains(">Day 4</text>"));
+    }
 }
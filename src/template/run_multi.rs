@@ -1,40 +1,112 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, path::Path, time::Duration};
 
+use crate::template::commands::Error;
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    calendar::{self, DaySummary},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Solutions that run longer than this are killed unless `--timeout` is passed. Does not include the time to build the bin.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// The per-day timeout for a `--timeout` in seconds. `0` disables the timeout.
+pub fn day_timeout(secs: Option<u64>) -> Option<Duration> {
+    match secs.unwrap_or(DEFAULT_TIMEOUT_SECS) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Outcome of running the solutions of several days.
+pub struct MultiRun {
+    /// Timings of all days that printed any output, if the run was timed.
+    pub timings: Option<Timings>,
+    /// Days whose solution did not compile, panicked, timed out or did not return an answer for every part.
+    pub failed: Vec<Day>,
+}
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    with_memory: bool,
+    timeout: Option<Duration>,
+) -> Result<MultiRun, Error> {
+    run_multi_in(
+        None,
+        days_to_run,
+        is_release,
+        is_timed,
+        with_memory,
+        timeout,
+    )
+}
+
+/// Same as [`run_multi`], but runs the solutions of the project checked out at `working_dir`, if given.
+pub fn run_multi_in(
+    working_dir: Option<&Path>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    with_memory: bool,
+    timeout: Option<Duration>,
+) -> Result<MultiRun, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut summaries: Vec<DaySummary> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if !child_commands::bin_exists(working_dir, day) {
+            println!("Not solved.");
+            summaries.push(DaySummary::not_scaffolded(day));
+            continue;
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
+        let output = child_commands::run_solution(
+            working_dir,
+            day,
+            is_timed,
+            is_release,
+            with_memory,
+            timeout,
+        )?;
+
+        let summary = DaySummary::from_output(day, &output.lines);
+
+        match (output.timed_out, timeout) {
+            (true, Some(timeout)) => {
+                println!("Timed out after {}s.", timeout.as_secs());
+                let mut val = child_commands::parse_exec_time(&output.lines, day);
+                val.timed_out = true;
                 timings.push(val);
+                summaries.push(summary.with_timeout(timeout));
             }
-        });
+            _ => {
+                if output.lines.is_empty() {
+                    println!("Not solved.");
+                } else {
+                    let val = child_commands::parse_exec_time(&output.lines, day);
+                    timings.push(val);
+                }
+                summaries.push(summary.with_exit_code(output.exit_code));
+            }
+        }
+    }
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -42,21 +114,20 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         );
         Some(timings)
     } else {
+        calendar::print(&summaries);
         None
-    }
-}
+    };
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
+    calendar::print_failures(&summaries);
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+    Ok(MultiRun {
+        timings,
+        failed: summaries
+            .iter()
+            .filter(|s| s.failure.is_some())
+            .map(|s| s.day)
+            .collect(),
+    })
 }
 
 #[must_use]
@@ -68,73 +139,169 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::memory::parse_bytes;
+    use crate::template::timings::parse_duration_nanos;
     use crate::template::Day;
     use std::{
-        io::{BufRead, BufReader},
+        env,
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Output of a solution bin.
+    pub struct SolutionOutput {
+        /// Lines printed to stdout.
+        pub lines: Vec<String>,
+        /// Exit code of the bin, `None` if it was terminated by a signal.
+        pub exit_code: Option<i32>,
+        /// Whether the bin was killed because it exceeded the timeout.
+        pub timed_out: bool,
+    }
+
+    /// Whether the solution bin of a day exists in the project at `working_dir`, or the current one.
+    pub fn bin_exists(working_dir: Option<&Path>, day: Day) -> bool {
+        let bin_path = get_path_for_bin(day);
+        working_dir.map_or_else(
+            || Path::new(&bin_path).exists(),
+            |dir| dir.join(&bin_path).exists(),
+        )
+    }
+
+    /// Run the solution bin for a given day. If it does not exit within `timeout`, it is killed.
+    ///
+    /// Solutions of a project in another `working_dir` are built into `target/baseline` of the current one,
+    /// so that repeated builds of the same revision are incremental.
+    pub fn run_solution(
+        working_dir: Option<&Path>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        with_memory: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !bin_exists(working_dir, day) {
+            return Ok(SolutionOutput {
+                lines: vec![],
+                exit_code: Some(0),
+                timed_out: false,
+            });
         }
 
         let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        if with_memory {
+            args.extend(["--features", "memory"]);
+        }
+
+        let cargo = |command: &str| -> Result<Command, Error> {
+            let mut cmd = Command::new("cargo");
+            cmd.arg(command).args(&args);
+
+            if let Some(dir) = working_dir {
+                let target_dir = env::current_dir()?.join("target").join("baseline");
+                cmd.current_dir(dir).env("CARGO_TARGET_DIR", target_dir);
+            }
+            Ok(cmd)
+        };
+
+        // build before the timeout starts, so that it only limits the time the solution runs.
+        if timeout.is_some() {
+            let status = cargo("build")?.stdout(Stdio::null()).status()?;
+            if !status.success() {
+                return Ok(SolutionOutput {
+                    lines: vec![],
+                    exit_code: status.code(),
+                    timed_out: false,
+                });
+            }
+        }
+
+        let mut cmd = cargo("run")?;
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.args(["--", "--time"]);
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || io::Error::from(io::ErrorKind::BrokenPipe);
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stderr_thread = thread::spawn(move || -> io::Result<()> {
+            for line in stderr.lines() {
+                eprintln!("{}", line?);
+            }
+            Ok(())
+        });
+
+        // stdout is read on a separate thread, so that waiting for a line can time out.
+        let (sender, receiver) = mpsc::channel();
+        let stdout_thread = thread::spawn(move || -> io::Result<()> {
+            for line in stdout.lines() {
+                let line = line?;
+                println!("{line}");
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+            Ok(())
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let timed_out = loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => output.push(line),
+                Err(RecvTimeoutError::Disconnected) => break false,
+                Err(RecvTimeoutError::Timeout) => break true,
+            }
+        };
+
+        if timed_out {
+            // the output threads are not joined: they finish once the killed bin closes its pipes.
+            cmd.kill()?;
+        } else {
+            for thread in [stdout_thread, stderr_thread] {
+                thread.join().map_err(|_| {
+                    io::Error::other("failed to forward the output of the solution")
+                })??;
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(SolutionOutput {
+            lines: output,
+            exit_code: status.code(),
+            timed_out,
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        let mut timings = super::Timing::new(day);
 
         output
             .iter()
@@ -143,19 +310,26 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(nanos) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, nanos, parse_memory(l), parse_samples(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, nanos, memory, samples)| {
+                let (peak_bytes, allocations) = memory.unzip();
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = Some(nanos);
+                    timings.part_1_peak_bytes = peak_bytes;
+                    timings.part_1_allocations = allocations;
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = Some(nanos);
+                    timings.part_2_peak_bytes = peak_bytes;
+                    timings.part_2_allocations = allocations;
+                    timings.part_2_samples = samples;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,29 +338,35 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    fn parse_time(line: &str) -> Option<f64> {
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        parse_duration_nanos(str_timing)
+    }
 
-        Some((str_timing, parsed_timing))
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Parses the peak bytes and the number of allocations, which are appended to the timing as
+    /// ` [<bytes> peak, <count> allocs]`.
+    fn parse_memory(line: &str) -> Option<(u64, u64)> {
+        let memory = line.strip_suffix(" allocs]")?.rsplit_once(" [")?.1;
+        let (peak, allocations) = memory.split_once(" peak, ")?;
+        Some((parse_bytes(peak)?, allocations.parse().ok()?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -220,8 +400,10 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 74130000_f64);
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
         }
 
         #[test]
@@ -235,8 +417,25 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 2000000000_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 100000000_f64);
+        }
+
+        #[test]
+        fn parses_memory_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [1.5KiB peak, 3 allocs]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_eq!(res.part_1_peak_bytes, Some(1536));
+            assert_eq!(res.part_1_allocations, Some(3));
+            assert_eq!(res.part_2_peak_bytes, None);
+            assert_eq!(res.part_2_allocations, None);
         }
 
         #[test]
@@ -250,8 +449,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_nanos.is_none(), true);
+            assert_eq!(res.part_2_nanos.is_none(), true);
         }
     }
 }
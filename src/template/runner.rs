@@ -4,32 +4,72 @@ use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env};
 
+use crate::template::aoc_cli::SubmissionOutcome;
+use crate::template::commands::Error;
+use crate::template::memory::{self, MemoryStats};
+use crate::template::progress;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Runs, prints and optionally submits a part. Errors are returned to the `main` of the solution, which exits with
+/// their exit code. A part that returns `None` fails with [`Error::NoAnswer`] after it was printed as `✖`.
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Result<(), Error> {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    #[cfg(feature = "trace")]
+    crate::template::trace::start(day, part);
+
+    let (result, duration, samples, memory) = run_timed(&func, input.clone(), |result| {
+        print_result(result, &part_str, "")
+    });
+
+    let stats_str = format_duration(&duration, samples) + &format_memory(memory);
+    print_result(&result, &part_str, &stats_str);
+
+    #[cfg(feature = "trace")]
+    crate::template::trace::finish(part);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    #[cfg(feature = "visualize")]
+    crate::template::viz::play(day, part);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    #[cfg(feature = "cpu-profile")]
+    crate::template::profile::run_part(&func, input, day, part);
+
+    let Some(result) = result else {
+        return Err(Error::NoAnswer(vec![part]));
+    };
+
+    if let Some(output) = submit_result(result, day, part)? {
+        let outcome = SubmissionOutcome::from_output(&output);
+        if let Err(e) = progress::record_submission(day, part, outcome) {
+            eprintln!("Failed to record progress: {e}");
+        }
     }
+
+    Ok(())
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// If the counting allocator is enabled, the heap usage of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<MemoryStats>) {
+    if memory::is_enabled() {
+        memory::reset();
+    }
+
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,6 +81,8 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
+    let memory = memory::is_enabled().then(memory::stats);
+
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
@@ -49,7 +91,7 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, memory)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -94,6 +136,16 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(String::new, |m| {
+        format!(
+            " [{} peak, {} allocs]",
+            memory::format_bytes(m.peak_bytes as u64),
+            m.allocations
+        )
+    })
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -132,38 +184,28 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Result<Option<Output>, Error> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return Ok(None);
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+    let usage =
+        || Error::Usage("Unexpected command-line input. Format: cargo solve 1 --submit 1".into());
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    let part_submit = args
+        .get(part_index)
+        .and_then(|x| x.parse::<u8>().ok())
+        .ok_or_else(usage)?;
 
     if part_submit != part {
-        return None;
+        return Ok(None);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    aoc_cli::check()?;
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Ok(Some(aoc_cli::submit(day, part, &result.to_string())?))
 }
@@ -0,0 +1,248 @@
+/// Runs a solution against generated inputs of increasing size and estimates its empirical complexity.
+use std::hint::black_box;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use crate::template::generator::{arg_value, Generator};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Settings for a scaling run, read from the command-line of the solution binary.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub seed: u64,
+    pub min_size: usize,
+    pub max_size: usize,
+    /// Stop growing the input once a single execution takes longer than this.
+    pub budget: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            min_size: 4,
+            max_size: 4096,
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `--seed`, `--min-size`, `--max-size` and `--budget-ms` from the process arguments, falling back to defaults.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let default = Self::default();
+        Self {
+            seed: arg_value("--seed").unwrap_or(default.seed),
+            min_size: arg_value("--min-size").unwrap_or(default.min_size).max(1),
+            max_size: arg_value("--max-size").unwrap_or(default.max_size),
+            budget: arg_value("--budget-ms").map_or(default.budget, Duration::from_millis),
+        }
+    }
+
+    /// Doubling input sizes from `min_size` up to `max_size`.
+    fn sizes(&self) -> impl Iterator<Item = usize> {
+        let max_size = self.max_size;
+        std::iter::successors(Some(self.min_size), |x| x.checked_mul(2))
+            .take_while(move |x| *x <= max_size)
+    }
+}
+
+/// Returns whether the solution binary was invoked by `cargo scale`.
+#[must_use]
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--scale")
+}
+
+/// Average execution time of a part for one input size.
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub size: usize,
+    pub duration: Duration,
+    pub samples: u32,
+}
+
+/// Time a function on a single input, repeating it for approx. 100ms (at most 100 times) when it is fast.
+fn measure<T>(func: impl Fn(&str) -> T, input: &str) -> (Duration, u32) {
+    let timer = Instant::now();
+    black_box(func(black_box(input)));
+    let base_time = timer.elapsed();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let samples =
+        (Duration::from_millis(100).as_nanos() / base_time.as_nanos().max(1)).clamp(1, 100) as u32;
+
+    if samples == 1 {
+        return (base_time, 1);
+    }
+
+    let timer = Instant::now();
+    for _ in 0..samples {
+        black_box(func(black_box(input)));
+    }
+
+    (timer.elapsed() / samples, samples)
+}
+
+/// Fits `duration = c * size^k` with a least-squares fit in log-log space and returns `k`.
+#[must_use]
+pub fn estimate_exponent(measurements: &[Measurement]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .filter(|m| !m.duration.is_zero())
+        .map(|m| ((m.size as f64).ln(), m.duration.as_secs_f64().ln()))
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+
+    if variance == 0.0 {
+        None
+    } else {
+        Some(covariance / variance)
+    }
+}
+
+/// Describes an exponent as the closest common complexity class, in terms of the generator `size`.
+#[must_use]
+pub fn describe_exponent(exponent: f64) -> String {
+    let rounded = (exponent * 2.0).round() / 2.0;
+    match rounded {
+        x if x <= 0.0 => "O(1)".into(),
+        x if (x - 1.0).abs() < f64::EPSILON => "O(n)".into(),
+        x if (x - 2.0).abs() < f64::EPSILON => "O(n²)".into(),
+        x if (x - 3.0).abs() < f64::EPSILON => "O(n³)".into(),
+        x => format!("O(n^{x})"),
+    }
+}
+
+/// Runs one part against generated inputs of doubling sizes and prints timings and the estimated complexity.
+pub fn run_part<T>(
+    day: Day,
+    part: u8,
+    generator: Option<Generator>,
+    func: impl Fn(&str) -> Option<T>,
+) {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} does not register a generator. \
+            Use `solution!({}, generator: ...)` to enable scaling.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    let config = Config::from_args();
+    let mut stdout = stdout();
+    let mut measurements = vec![];
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    for size in config.sizes() {
+        let input = generator(config.seed, size);
+
+        print!("{size:>8} {ANSI_ITALIC}running{ANSI_RESET}");
+        let _ = stdout.flush();
+
+        let (duration, samples) = measure(&func, &input);
+        let measurement = Measurement {
+            size,
+            duration,
+            samples,
+        };
+
+        let growth = measurements
+            .last()
+            .map(|previous: &Measurement| {
+                format!(
+                    " x{:.2}",
+                    duration.as_secs_f64() / previous.duration.as_secs_f64().max(f64::EPSILON)
+                )
+            })
+            .unwrap_or_default();
+
+        print!("\r");
+        println!(
+            "{size:>8} {:>12}{growth} {ANSI_ITALIC}({samples} samples){ANSI_RESET}",
+            format!("{duration:.1?}")
+        );
+
+        measurements.push(measurement);
+
+        if duration > config.budget {
+            break;
+        }
+    }
+
+    match estimate_exponent(&measurements) {
+        Some(exponent) => println!(
+            "Estimated complexity: {ANSI_BOLD}{}{ANSI_RESET} {ANSI_ITALIC}(exponent {exponent:.2}){ANSI_RESET}",
+            describe_exponent(exponent)
+        ),
+        None => println!("Estimated complexity: not enough measurements."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{describe_exponent, estimate_exponent, Config, Measurement};
+
+    fn measurements(f: impl Fn(f64) -> f64) -> Vec<Measurement> {
+        [8, 16, 32, 64, 128]
+            .into_iter()
+            .map(|size| Measurement {
+                size,
+                duration: Duration::from_secs_f64(f(size as f64)),
+                samples: 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn estimates_linear_growth() {
+        let exponent = estimate_exponent(&measurements(|n| n * 1e-6)).unwrap();
+        assert!((exponent - 1.0).abs() < 0.01);
+        assert_eq!(describe_exponent(exponent), "O(n)");
+    }
+
+    #[test]
+    fn estimates_quadratic_growth() {
+        let exponent = estimate_exponent(&measurements(|n| n * n * 1e-6)).unwrap();
+        assert!((exponent - 2.0).abs() < 0.01);
+        assert_eq!(describe_exponent(exponent), "O(n²)");
+    }
+
+    #[test]
+    fn needs_two_measurements() {
+        assert!(estimate_exponent(&measurements(|n| n)[..1]).is_none());
+    }
+
+    #[test]
+    fn describes_fractional_exponents() {
+        assert_eq!(describe_exponent(0.1), "O(1)");
+        assert_eq!(describe_exponent(1.4), "O(n^1.5)");
+    }
+
+    #[test]
+    fn doubles_sizes() {
+        let config = Config {
+            min_size: 4,
+            max_size: 40,
+            ..Config::default()
+        };
+        assert_eq!(config.sizes().collect::<Vec<_>>(), vec![4, 8, 16, 32]);
+    }
+}
@@ -0,0 +1,210 @@
+/// Templates that `cargo scaffold` creates solution modules from.
+///
+/// Besides the built-in templates, templates can be added to the `templates` directory of the project as
+/// `templates/<name>.txt`. A project template with the name of a built-in template takes precedence.
+use std::fmt::Display;
+use std::path::Path;
+use std::{env, fs, io};
+
+use crate::template::manifest::{self, Manifest};
+use crate::template::Day;
+
+static PROJECT_TEMPLATE_DIR: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_RETURN_TYPE: &str = "u32";
+
+static BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "nom",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/nom.txt"
+        )),
+    ),
+    (
+        "shared-parse",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/shared-parse.txt"
+        )),
+    ),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name) => write!(
+                f,
+                "unknown template \"{name}\", available templates: {}",
+                available().join(", ")
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Names of all templates, built-in and from the project.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(PROJECT_TEMPLATE_DIR) {
+        for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    if !names.iter().any(|n| n == name) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// Load a template by name, preferring templates of the project over built-in ones.
+pub fn load(name: &str) -> Result<String, Error> {
+    let path = Path::new(PROJECT_TEMPLATE_DIR).join(format!("{name}.txt"));
+    if path.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| Error::NotFound(name.to_string()))
+}
+
+/// Values that are filled into a template.
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub return_type: String,
+}
+
+impl Placeholders {
+    pub fn new(day: Day, return_type: Option<String>) -> Self {
+        Self {
+            day,
+            year: env::var("AOC_YEAR").ok().and_then(|y| y.parse().ok()),
+            title: puzzle_title(day),
+            return_type: return_type.unwrap_or_else(|| DEFAULT_RETURN_TYPE.into()),
+        }
+    }
+}
+
+/// Fill in the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day, e.g. `1`.
+///  - `%DAY%`: the zero-padded day, e.g. `01`.
+///  - `%YEAR%`: the year set in `AOC_YEAR`.
+///  - `%TITLE%`: the puzzle title from the downloaded description, `Day 1` if it was not downloaded yet.
+///  - `%HEADING%`: the day and its title, e.g. `Day 1: Historian Hysteria`. Used as the module doc comment, which is
+///    completed with the title once the description is downloaded.
+///  - `%RETURN_TYPE%`: the return type of both parts, `u32` unless passed with `--return-type`.
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    let day = placeholders.day;
+    let title = placeholders
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace(
+            "%YEAR%",
+            &placeholders.year.map(|y| y.to_string()).unwrap_or_default(),
+        )
+        .replace(
+            "%HEADING%",
+            &manifest::heading(day, placeholders.title.as_deref()),
+        )
+        .replace("%TITLE%", &title)
+        .replace("%RETURN_TYPE%", &placeholders.return_type)
+}
+
+/// Look up the title of a puzzle in the manifest, or in its description if it was downloaded before.
+/// Only `download` adds puzzles to the manifest.
+fn puzzle_title(day: Day) -> Option<String> {
+    Manifest::read_from_file()
+        .title(day)
+        .map(ToString::to_string)
+        .or_else(|| manifest::read_title(day).ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render, Placeholders, BUILTIN_TEMPLATES};
+    use crate::day;
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, _) in BUILTIN_TEMPLATES {
+            assert!(load(name).unwrap().contains("%DAY_NUMBER%"));
+        }
+        assert!(load("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(7),
+            year: Some(2024),
+            title: Some("Bridge Repair".into()),
+            return_type: "u64".into(),
+        };
+
+        let template = "solution!(%DAY_NUMBER%); // %DAY% %YEAR% %TITLE% -> Option<%RETURN_TYPE%>";
+        assert_eq!(
+            render(template, &placeholders),
+            "solution!(7); // 07 2024 Bridge Repair -> Option<u64>"
+        );
+        assert_eq!(render("%HEADING%", &placeholders), "Day 7: Bridge Repair");
+    }
+
+    #[test]
+    fn falls_back_to_day_without_title() {
+        let placeholders = Placeholders {
+            day: day!(7),
+            year: None,
+            title: None,
+            return_type: "u32".into(),
+        };
+        assert_eq!(render("%TITLE% %YEAR%", &placeholders), "Day 7 ");
+        assert_eq!(render("%HEADING%", &placeholders), "Day 7");
+    }
+}
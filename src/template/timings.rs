@@ -1,17 +1,36 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
+use crate::template::memory::{format_bytes, parse_bytes};
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON schema timings are stored in.
+/// Version 1 stored the duration of each part as a formatted string, e.g. `"74.13ns"`.
+/// Version 2 stores each part in nanoseconds and formats them only when displayed.
+/// Version 3 stores the peak heap usage in bytes instead of a formatted string, e.g. `"1.5KiB"`.
+const SCHEMA_VERSION: u32 = 3;
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Average duration of each part in nanoseconds.
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
     pub total_nanos: f64,
+    /// Peak heap usage of each part in bytes, if measured with the `memory` feature.
+    pub part_1_peak_bytes: Option<u64>,
+    pub part_2_peak_bytes: Option<u64>,
+    /// Number of heap allocations of each part, if measured with the `memory` feature.
+    pub part_1_allocations: Option<u64>,
+    pub part_2_allocations: Option<u64>,
+    /// Number of samples the timing of each part was averaged over.
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Whether the solution was killed because it exceeded the per-day timeout.
+    pub timed_out: bool,
 }
 
 /// Represents benchmark times for a set of days.
@@ -37,12 +56,15 @@ impl Timings {
             .unwrap_or_default()
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `new` if present. Days that timed out in `new` keep their
+    /// timing in `self`, so that a run that was killed does not replace a complete one.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            if !(timing.timed_out && self.data.iter().any(|t| t.day == timing.day)) {
+                data.push(timing.clone());
+            }
         }
 
         for timing in &self.data {
@@ -51,10 +73,19 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Sum up the duration of one part over all timings as millis. Parts without a timing are skipped.
+    pub fn part_total_millis(&self, part: u8) -> f64 {
+        self.data
+            .iter()
+            .filter_map(|t| t.part_nanos(part))
+            .sum::<f64>()
+            / 1_000_000_f64
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -63,16 +94,92 @@ impl Timings {
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1_nanos.is_some() && t.part_2_nanos.is_some())
+    }
+}
+
+impl Timing {
+    /// A timing of `day` without any measurements.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            total_nanos: 0_f64,
+            part_1_peak_bytes: None,
+            part_2_peak_bytes: None,
+            part_1_allocations: None,
+            part_2_allocations: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            timed_out: false,
+        }
+    }
+
+    /// Returns the duration of a part (1 or 2) in nanoseconds.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1_nanos,
+            2 => self.part_2_nanos,
+            _ => None,
+        }
+    }
+
+    /// Returns the duration of a part (1 or 2) formatted for display, e.g. `74.1ns`.
+    pub fn format_part(&self, part: u8) -> Option<String> {
+        self.part_nanos(part).map(format_duration_nanos)
+    }
+
+    /// Returns the peak heap usage of a part (1 or 2) formatted for display, e.g. `1.5KiB`.
+    pub fn format_peak_bytes(&self, part: u8) -> Option<String> {
+        match part {
+            1 => self.part_1_peak_bytes,
+            2 => self.part_2_peak_bytes,
+            _ => None,
+        }
+        .map(format_bytes)
+    }
+
+    /// Whether the heap usage of any part was measured.
+    pub fn has_memory(&self) -> bool {
+        self.part_1_peak_bytes.is_some() || self.part_2_peak_bytes.is_some()
+    }
+}
+
+/// Parses a duration formatted by the runner (e.g. `74.13ns` or `1.2ms`) to nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.parse().ok()
+    }
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
+/// Formats nanoseconds the same way the runner prints durations, e.g. `74.1ns` or `1.2ms`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_duration_nanos(nanos: f64) -> String {
+    format!(
+        "{:.1?}",
+        Duration::from_nanos(nanos.max(0.0).round() as u64)
+    )
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -87,10 +194,31 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
-        let json_data = json
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // documents without a version predate versioning and are migrated by `Timing::try_from`.
+        let version = json
+            .get("version")
+            .and_then(|v| v.get::<f64>())
+            .copied()
+            .unwrap_or(1.0);
+        if version > f64::from(SCHEMA_VERSION) {
+            return Err(format!(
+                "timings were stored with schema version {version}, only versions up to {SCHEMA_VERSION} are supported."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -114,24 +242,30 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, nanos) in [
+            ("part_1_nanos", value.part_1_nanos),
+            ("part_2_nanos", value.part_2_nanos),
+        ] {
+            map.insert(key.into(), nanos.map_or(JsonValue::Null, JsonValue::Number));
+        }
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, count) in [
+            ("part_1_peak_bytes", value.part_1_peak_bytes),
+            ("part_2_peak_bytes", value.part_2_peak_bytes),
+            ("part_1_allocations", value.part_1_allocations),
+            ("part_2_allocations", value.part_2_allocations),
+            ("part_1_samples", value.part_1_samples),
+            ("part_2_samples", value.part_2_samples),
+        ] {
+            if let Some(count) = count {
+                #[allow(clippy::cast_precision_loss)]
+                map.insert(key.into(), JsonValue::Number(count as f64));
+            }
+        }
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        if value.timed_out {
+            map.insert("timed_out".into(), JsonValue::Boolean(true));
+        }
 
         JsonValue::Object(map)
     }
@@ -151,30 +285,78 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part_1_nanos = part_nanos_from_json(json, 1)?;
+        let part_2_nanos = part_nanos_from_json(json, 2)?;
+
+        // memory and sample keys are optional, timings stored before they were introduced do not have them.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let [part_1_peak_bytes, part_2_peak_bytes] = [1, 2].map(|part| {
+            count(&format!("part_{part}_peak_bytes")).or_else(|| {
+                // schema version 2 stored the peak heap usage as a formatted string.
+                json.get(&format!("part_{part}_memory"))
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|s| parse_bytes(s))
+            })
+        });
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<bool>().copied())
+            .unwrap_or_default();
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1_nanos,
+            part_2_nanos,
             total_nanos,
+            part_1_peak_bytes,
+            part_2_peak_bytes,
+            part_1_allocations: count("part_1_allocations"),
+            part_2_allocations: count("part_2_allocations"),
+            part_1_samples: count("part_1_samples"),
+            part_2_samples: count("part_2_samples"),
+            timed_out,
         })
     }
 }
 
+/// Reads the duration of a part, migrating timings stored as formatted strings (schema version 1).
+fn part_nanos_from_json(
+    json: &HashMap<String, JsonValue>,
+    part: u8,
+) -> Result<Option<f64>, String> {
+    if let Some(value) = json.get(&format!("part_{part}_nanos")) {
+        return match value {
+            JsonValue::Null => Ok(None),
+            JsonValue::Number(nanos) => Ok(Some(*nanos)),
+            _ => Err(format!(
+                "Expected timing.part_{part}_nanos to be null or a number."
+            )),
+        };
+    }
+
+    match json.get(&format!("part_{part}")) {
+        Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => parse_duration_nanos(s)
+            .map(Some)
+            .ok_or_else(|| format!("Could not parse timing.part_{part} \"{s}\" as a duration.")),
+        _ => Err(format!(
+            "Expected timing.part_{part}_nanos to be null or a number."
+        )),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -187,22 +369,21 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10_000_000.0),
+                    part_2_nanos: Some(20_000_000.0),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30_000_000.0),
+                    part_2_nanos: Some(40_000_000.0),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(40_000_000.0),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -218,11 +399,50 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_1_nanos, Some(1_000_000.0));
+            assert_eq!(timing.part_2_nanos, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_memory": "1.0KiB", "part_2_memory": "2.0MiB" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_peak_bytes, Some(1024));
+            assert_eq!(timing.part_2_peak_bytes, Some(2 * 1024 * 1024));
+            assert_eq!(timing.part_1_allocations, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_memory_counts() {
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "part_1_nanos": 1500, "part_2_nanos": null, "total_nanos": 1500, "part_1_peak_bytes": 1536, "part_1_allocations": 3 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_peak_bytes, Some(1536));
+            assert_eq!(timing.part_1_allocations, Some(3));
+            assert_eq!(timing.format_peak_bytes(1), Some("1.5KiB".into()));
+            assert_eq!(timing.format_peak_bytes(2), None);
+        }
+
+        #[test]
+        fn handles_versioned_json_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1_nanos": 1500, "part_2_nanos": null, "total_nanos": 1500, "part_1_samples": 10000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(1500.0));
+            assert_eq!(timing.part_2_nanos, None);
+            assert_eq!(timing.part_1_samples, Some(10000));
+            assert_eq!(timing.format_part(1), Some("1.5µs".into()));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_schema_versions() {
+            let json = r#"{ "version": 4, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,9 +467,31 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 3);
+            assert_eq!(timings.data[0].part_1_nanos, Some(10_000_000.0));
+            assert_eq!(timings.data[2].part_2_nanos, None);
+            assert!(!timings.data[2].timed_out);
+        }
+
+        #[test]
+        fn roundtrips_timed_out_days() {
+            let mut timings = get_mock_timings();
+            timings.data[2].timed_out = true;
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert!(!timings.data[0].timed_out);
+            assert!(timings.data[2].timed_out);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -278,42 +520,36 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1_nanos: Some(1_000_000.0),
+                    part_2_nanos: Some(2_000_000.0),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(1_000_000.0),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -329,12 +565,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +580,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +591,32 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_timings_of_days_that_timed_out() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![
+                    Timing {
+                        timed_out: true,
+                        ..Timing::new(day!(2))
+                    },
+                    Timing {
+                        timed_out: true,
+                        ..Timing::new(day!(3))
+                    },
+                ],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[1].part_1_nanos, Some(30_000_000.0));
+            assert!(!merged.data[1].timed_out);
+            assert_eq!(merged.data[2].day, day!(3));
+            assert!(merged.data[2].timed_out);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,213 @@
+/// Debug tracing for solutions, enabled by `cargo solve --trace`.
+///
+/// Events are written as JSON lines to `data/traces/<day>.jsonl` instead of the terminal, so they neither get lost
+/// in nor garble the output of the runner. Without the `trace` feature, [`trace!`](crate::trace) compiles to nothing.
+#[cfg(feature = "trace")]
+pub use tracer::{finish, start};
+
+/// Whether solutions were built with tracing.
+pub const ENABLED: bool = cfg!(feature = "trace");
+
+/// Records an event with a message, either formatted like [`format!`] or followed by `key = value` fields:
+///
+/// ```ignore
+/// trace!("machine {i} has no solution");
+/// trace!("no solution", d = d, di = di);
+/// ```
+///
+/// The values of fields are recorded with their [`Debug`](std::fmt::Debug) representation.
+#[macro_export]
+macro_rules! trace {
+    ($message:literal $(, $key:ident = $value:expr)+ $(,)?) => {
+        if $crate::template::trace::ENABLED {
+            $crate::template::trace::event(
+                concat!(file!(), ":", line!()),
+                $message,
+                &[$( (stringify!($key), format!("{:?}", $value)) ),+],
+            );
+        }
+    };
+    ($($arg:tt)+) => {
+        if $crate::template::trace::ENABLED {
+            $crate::template::trace::event(concat!(file!(), ":", line!()), &format!($($arg)+), &[]);
+        }
+    };
+}
+
+/// Writes an event to the trace of the running part. Use [`trace!`](crate::trace) instead.
+#[doc(hidden)]
+pub fn event(location: &str, message: &str, fields: &[(&str, String)]) {
+    #[cfg(feature = "trace")]
+    tracer::write(location, message, fields);
+
+    #[cfg(not(feature = "trace"))]
+    let _ = (location, message, fields);
+}
+
+#[cfg(feature = "trace")]
+mod tracer {
+    use std::collections::HashMap;
+    use std::fs::{self, File};
+    use std::io::{BufWriter, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    use tinyjson::JsonValue;
+
+    use crate::template::{Day, ANSI_DIM, ANSI_RESET};
+
+    struct Tracer {
+        path: PathBuf,
+        file: BufWriter<File>,
+        part: u8,
+        start: Instant,
+        events: usize,
+    }
+
+    static TRACER: Mutex<Option<Tracer>> = Mutex::new(None);
+
+    /// Starts tracing a part. The trace file of the day is truncated when the first part starts.
+    pub fn start(day: Day, part: u8) {
+        start_in(&PathBuf::from("data").join("traces"), day, part);
+    }
+
+    pub(super) fn start_in(dir: &Path, day: Day, part: u8) {
+        let mut tracer = TRACER.lock().unwrap_or_else(|e| e.into_inner());
+
+        if tracer.is_none() {
+            let path = dir.join(format!("{day}.jsonl"));
+            match fs::create_dir_all(dir).and_then(|()| File::create(&path)) {
+                Ok(file) => {
+                    *tracer = Some(Tracer {
+                        path,
+                        file: BufWriter::new(file),
+                        part,
+                        start: Instant::now(),
+                        events: 0,
+                    });
+                }
+                Err(e) => eprintln!("Failed to create trace file {}: {e}", path.display()),
+            }
+        }
+
+        if let Some(tracer) = tracer.as_mut() {
+            tracer.part = part;
+            tracer.start = Instant::now();
+            tracer.events = 0;
+        }
+    }
+
+    /// Flushes the trace and prints where the events of the part were written.
+    pub fn finish(part: u8) {
+        let mut tracer = TRACER.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(tracer) = tracer.as_mut() else {
+            return;
+        };
+
+        if let Err(e) = tracer.file.flush() {
+            eprintln!("Failed to write trace file {}: {e}", tracer.path.display());
+        }
+        println!(
+            "Part {part}: {ANSI_DIM}{} trace events in {}{ANSI_RESET}",
+            tracer.events,
+            tracer.path.display()
+        );
+    }
+
+    pub(super) fn write(location: &str, message: &str, fields: &[(&str, String)]) {
+        let mut tracer = TRACER.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(tracer) = tracer.as_mut() else {
+            return;
+        };
+
+        let fields: HashMap<String, JsonValue> = fields
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), JsonValue::String(value.clone())))
+            .collect();
+
+        let mut event: HashMap<String, JsonValue> = HashMap::new();
+        event.insert("part".into(), JsonValue::Number(f64::from(tracer.part)));
+        #[allow(clippy::cast_precision_loss)]
+        event.insert(
+            "elapsed_us".into(),
+            JsonValue::Number(tracer.start.elapsed().as_micros() as f64),
+        );
+        event.insert("location".into(), JsonValue::String(location.into()));
+        event.insert("message".into(), JsonValue::String(message.into()));
+        if !fields.is_empty() {
+            event.insert("fields".into(), JsonValue::Object(fields));
+        }
+
+        if let Ok(line) = JsonValue::Object(event).stringify() {
+            if writeln!(tracer.file, "{line}").is_ok() {
+                tracer.events += 1;
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[test]
+    fn accepts_messages_and_fields() {
+        let (a, b) = (1, "two");
+
+        crate::trace!("a is {a}, b is {}", b);
+        crate::trace!("values", a = a, b = b);
+        crate::trace!("values", sum = a + 1,);
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn writes_events_as_json_lines() {
+        use std::collections::HashMap;
+        use std::{env, fs, process};
+
+        use tinyjson::JsonValue;
+
+        use crate::day;
+
+        let dir = env::temp_dir().join(format!("aoc-trace-{}", process::id()));
+        super::tracer::start_in(&dir, day!(7), 2);
+        crate::trace!("found {} paths", 3);
+        crate::trace!("no solution", d = 4, name = "x");
+        super::finish(2);
+
+        let trace = fs::read_to_string(dir.join("07.jsonl")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // other tests may trace concurrently, so only look at the events of this one.
+        let events: Vec<JsonValue> = trace
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .filter(|event: &JsonValue| {
+                let message: &String = event["message"].get().unwrap();
+                message == "found 3 paths" || message == "no solution"
+            })
+            .collect();
+
+        assert_eq!(events.len(), 2);
+        for event in &events {
+            assert_eq!(event["part"], JsonValue::Number(2.0));
+            assert!(matches!(event["elapsed_us"], JsonValue::Number(_)));
+        }
+        assert_eq!(
+            events[0]["message"],
+            JsonValue::String("found 3 paths".into())
+        );
+        let first: &HashMap<_, _> = events[0].get().unwrap();
+        assert!(!first.contains_key("fields"));
+        assert_eq!(
+            events[1]["message"],
+            JsonValue::String("no solution".into())
+        );
+        assert_eq!(events[1]["fields"]["d"], JsonValue::String("4".into()));
+        assert_eq!(
+            events[1]["fields"]["name"],
+            JsonValue::String("\"x\"".into())
+        );
+    }
+}
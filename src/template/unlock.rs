@@ -0,0 +1,215 @@
+/// Waits for the next puzzle to unlock, for `cargo today --wait`.
+///
+/// Puzzles unlock at midnight in the timezone of the Advent of Code servers (UTC-5). The clock is passed in as a
+/// [`Clock`], so that waiting can be tested without sleeping.
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET, EVENT_YEAR, SERVER_UTC_OFFSET};
+
+/// `--wait` refuses to wait for puzzles that unlock later than this.
+const MAX_WAIT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Waiting continues for this long after the unlock, in case the local clock is slightly ahead of the server.
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// The clock of the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// The next day of the event to unlock after `now`, and when it unlocks.
+pub fn next_unlock(now: DateTime<Utc>) -> Option<(Day, DateTime<Utc>)> {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+    let year = EVENT_YEAR.map_or_else(|| now.with_timezone(&offset).year(), i32::from);
+
+    all_days().find_map(|day| {
+        let unlock = offset
+            .with_ymd_and_hms(year, 12, u32::from(day.into_inner()), 0, 0, 0)
+            .single()?
+            .with_timezone(&Utc);
+        (unlock > now).then_some((day, unlock))
+    })
+}
+
+/// Returns the day that unlocked today if it was not scaffolded yet. Otherwise, waits with a countdown on `out` for
+/// the next day, if it unlocks within 24 hours.
+pub fn wait_for_unlock(
+    clock: &mut impl Clock,
+    out: &mut impl Write,
+    is_scaffolded: impl Fn(Day) -> bool,
+) -> Result<Day, String> {
+    if let Some(day) = Day::today_at(clock.now()).filter(|day| !is_scaffolded(*day)) {
+        return Ok(day);
+    }
+
+    let Some((day, unlock)) = next_unlock(clock.now()) else {
+        return Err("all puzzles of the event have unlocked already.".into());
+    };
+
+    let until_unlock = (unlock - clock.now()).to_std().unwrap_or_default();
+    if until_unlock > MAX_WAIT {
+        return Err(format!(
+            "day {day} unlocks in {}, `--wait` waits for at most 24 hours.",
+            format_countdown(until_unlock)
+        ));
+    }
+
+    let target = unlock + UNLOCK_GRACE;
+    loop {
+        let remaining = (target - clock.now()).to_std().unwrap_or_default();
+        if remaining.is_zero() {
+            break;
+        }
+
+        let _ = write!(
+            out,
+            "\r⏳ Day {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            format_countdown(remaining)
+        );
+        let _ = out.flush();
+
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    let _ = writeln!(out, "\r🎄 Day {day} unlocked!{}", " ".repeat(20));
+    Ok(day)
+}
+
+/// Formats a duration as `hh:mm:ss`, rounding up to full seconds.
+fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{format_countdown, next_unlock, wait_for_unlock, Clock};
+    use crate::day;
+    use crate::template::EVENT_YEAR;
+
+    /// A clock that advances only when sleeping.
+    struct FakeClock {
+        now: DateTime<Utc>,
+        sleeps: usize,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += chrono::Duration::from_std(duration).unwrap();
+            self.sleeps += 1;
+        }
+    }
+
+    fn year() -> i32 {
+        EVENT_YEAR.map_or(2024, i32::from)
+    }
+
+    /// Midnight of a day of december at the server (UTC-5) in UTC, plus `secs`.
+    fn server_midnight(day: u32, secs: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year(), 12, day, 5, 0, 0).unwrap() + chrono::Duration::seconds(secs)
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let (day, unlock) = next_unlock(server_midnight(1, -10)).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(unlock, server_midnight(1, 0));
+
+        let (day, _) = next_unlock(server_midnight(3, 0)).unwrap();
+        assert_eq!(day, day!(4));
+    }
+
+    #[test]
+    fn waits_for_unlock() {
+        let mut clock = FakeClock {
+            now: server_midnight(1, -5),
+            sleeps: 0,
+        };
+        let mut out = vec![];
+
+        assert_eq!(
+            wait_for_unlock(&mut clock, &mut out, |_| false),
+            Ok(day!(1))
+        );
+        assert!(clock.now >= server_midnight(1, 0));
+        assert_eq!(clock.sleeps, 7);
+        assert!(String::from_utf8(out).unwrap().contains("00:00:05"));
+    }
+
+    #[test]
+    fn waits_for_next_day_once_today_is_scaffolded() {
+        // 23:59:55 at the server on december 3rd.
+        let mut clock = FakeClock {
+            now: server_midnight(4, -5),
+            sleeps: 0,
+        };
+        let mut out = vec![];
+
+        assert_eq!(
+            wait_for_unlock(&mut clock, &mut out, |day| day == day!(3)),
+            Ok(day!(4))
+        );
+        assert!(clock.now >= server_midnight(4, 0));
+        assert_eq!(clock.sleeps, 7);
+    }
+
+    #[test]
+    fn returns_unlocked_days_immediately() {
+        let mut clock = FakeClock {
+            now: server_midnight(2, 3600),
+            sleeps: 0,
+        };
+
+        assert_eq!(
+            wait_for_unlock(&mut clock, &mut vec![], |_| false),
+            Ok(day!(2))
+        );
+        assert_eq!(clock.sleeps, 0);
+    }
+
+    #[test]
+    fn refuses_to_wait_for_days() {
+        let mut clock = FakeClock {
+            now: server_midnight(1, -3 * 24 * 3600),
+            sleeps: 0,
+        };
+
+        assert!(wait_for_unlock(&mut clock, &mut vec![], |_| false).is_err());
+        assert_eq!(clock.sleeps, 0);
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(
+            format_countdown(Duration::from_millis(3_661_500)),
+            "01:01:02"
+        );
+    }
+}
@@ -0,0 +1,391 @@
+/// Frames of grid simulations, played back in the terminal or exported as a GIF by `cargo solve --visualize`.
+///
+/// Solutions emit frames with [`frame`]. Without the `visualize` feature, [`frame`] does nothing and never calls the
+/// closure that builds the frame, so visualizations can stay in a solution at no cost.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// An animation in the terminal.
+    #[default]
+    Terminal,
+    /// An animated GIF in `visualizations/`.
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "terminal" => Ok(Format::Terminal),
+            "gif" => Ok(Format::Gif),
+            other => Err(format!(
+                "unknown visualization format `{other}`, expected `terminal` or `gif`."
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Terminal => "terminal",
+            Format::Gif => "gif",
+        })
+    }
+}
+
+/// A snapshot of a character grid, with some of its cells highlighted. Cells are stored as ASCII bytes, other
+/// characters are shown as `?`.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "visualize"), allow(dead_code))]
+pub struct Frame {
+    rows: usize,
+    cols: usize,
+    cells: Vec<u8>,
+    highlighted: Vec<bool>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Builds a `rows` x `cols` frame from a cell function, like [`crate::template::generator::grid`].
+    pub fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                u8::try_from(cell(row, col))
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .unwrap_or(b'?')
+            })
+            .collect();
+
+        Self {
+            rows,
+            cols,
+            cells,
+            highlighted: vec![false; rows * cols],
+            caption: None,
+        }
+    }
+
+    /// Highlights the cells at `(row, col)`. Cells outside of the grid are ignored.
+    #[must_use]
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        for (row, col) in cells {
+            if row < self.rows && col < self.cols {
+                self.highlighted[row * self.cols + col] = true;
+            }
+        }
+        self
+    }
+
+    /// Sets a line of text that is shown below the grid in the terminal.
+    #[must_use]
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+}
+
+/// Records the frame built by `build`, if the solution is visualized.
+#[inline]
+pub fn frame(build: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    recording::push(build);
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = build;
+}
+
+#[cfg(feature = "visualize")]
+pub use recording::play;
+
+#[cfg(feature = "visualize")]
+mod recording {
+    use std::borrow::Cow;
+    use std::fs::{self, File};
+    use std::io::{stdout, Write};
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{Format, Frame};
+    use crate::template::generator::arg_value;
+    use crate::template::{Day, ANSI_BOLD, ANSI_DIM, ANSI_RESET, ANSI_YELLOW};
+
+    /// Long simulations are sampled down to this many frames while they are recorded, and further down for playback
+    /// in the terminal.
+    const MAX_GIF_FRAMES: usize = 600;
+    const MAX_TERMINAL_FRAMES: usize = 300;
+
+    const TERMINAL_DELAY: Duration = Duration::from_millis(40);
+
+    /// Delay between the frames of a GIF, in hundredths of a second.
+    const GIF_DELAY: u16 = 4;
+
+    /// Width of the largest side of a GIF in pixels, and bounds for the size of a cell.
+    const GIF_SIZE: usize = 600;
+    const MAX_CELL_PX: usize = 8;
+
+    /// Background for `.` and ` `, highlighted cells, then colors for all other characters.
+    const PALETTE: [[u8; 3]; 16] = [
+        [0x0f, 0x0f, 0x23],
+        [0xff, 0xff, 0x66],
+        [0xcc, 0xcc, 0xcc],
+        [0x00, 0x99, 0x00],
+        [0x66, 0x99, 0xff],
+        [0xff, 0x66, 0x66],
+        [0xcc, 0x88, 0xff],
+        [0x66, 0xcc, 0xcc],
+        [0xff, 0x99, 0x33],
+        [0x99, 0x66, 0x33],
+        [0x33, 0x66, 0x99],
+        [0x99, 0xcc, 0x66],
+        [0xcc, 0x66, 0x99],
+        [0x66, 0x66, 0x66],
+        [0x33, 0x99, 0x66],
+        [0xff, 0xcc, 0x99],
+    ];
+
+    /// Every `stride`-th of the emitted frames. Once `MAX_GIF_FRAMES` are kept, every other frame is dropped and the
+    /// stride doubles, so that the recording stays evenly spaced and bounded without building skipped frames.
+    pub(super) struct Recording {
+        pub(super) frames: Vec<Frame>,
+        stride: usize,
+        pub(super) emitted: usize,
+    }
+
+    impl Recording {
+        pub(super) const fn new() -> Self {
+            Self {
+                frames: Vec::new(),
+                stride: 1,
+                emitted: 0,
+            }
+        }
+
+        pub(super) fn push(&mut self, build: impl FnOnce() -> Frame) {
+            let index = self.emitted;
+            self.emitted += 1;
+            if !index.is_multiple_of(self.stride) {
+                return;
+            }
+
+            // the recording is full after `MAX_GIF_FRAMES * stride` frames, so `index` is a multiple of the next
+            // stride as well.
+            if self.frames.len() == MAX_GIF_FRAMES {
+                let mut kept = false;
+                self.frames.retain(|_| {
+                    kept = !kept;
+                    kept
+                });
+                self.stride *= 2;
+            }
+            self.frames.push(build());
+        }
+    }
+
+    static RECORDING: Mutex<Recording> = Mutex::new(Recording::new());
+
+    pub(super) fn push(build: impl FnOnce() -> Frame) {
+        RECORDING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(build);
+    }
+
+    /// Plays back or exports the frames that a part emitted, in the format passed as `--visualize`.
+    pub fn play(day: Day, part: u8) {
+        let recording = std::mem::replace(
+            &mut *RECORDING.lock().unwrap_or_else(|e| e.into_inner()),
+            Recording::new(),
+        );
+        if recording.frames.is_empty() {
+            return;
+        }
+
+        match arg_value("--visualize").unwrap_or_default() {
+            Format::Terminal => {
+                let shown = sample(&recording.frames, MAX_TERMINAL_FRAMES);
+                play_terminal(&shown);
+                println!(
+                    "Part {part}: {ANSI_DIM}showed {} of {} frames{ANSI_RESET}",
+                    shown.len(),
+                    recording.emitted
+                );
+            }
+            Format::Gif => {
+                let shown: Vec<&Frame> = recording.frames.iter().collect();
+                match write_gif(day, part, &shown) {
+                    Ok(path) => println!(
+                        "Part {part}: {ANSI_BOLD}{}{ANSI_RESET} {ANSI_DIM}({} of {} frames){ANSI_RESET}",
+                        path.display(),
+                        shown.len(),
+                        recording.emitted
+                    ),
+                    Err(e) => eprintln!("Part {part}: failed to write GIF: {e}"),
+                }
+            }
+        }
+    }
+
+    /// Picks at most `max` frames at even intervals, always keeping the last one.
+    fn sample(frames: &[Frame], max: usize) -> Vec<&Frame> {
+        if frames.len() <= max {
+            return frames.iter().collect();
+        }
+        (0..max)
+            .map(|i| &frames[i * (frames.len() - 1) / (max - 1)])
+            .collect()
+    }
+
+    fn play_terminal(frames: &[&Frame]) {
+        let mut stdout = stdout();
+
+        // each frame is drawn over the previous one, leaving the output above and the last frame in place.
+        let mut height = 0;
+
+        for (i, frame) in frames.iter().enumerate() {
+            let mut screen = if height > 0 {
+                format!("\x1b[{height}A")
+            } else {
+                String::new()
+            };
+            for row in 0..frame.rows {
+                for col in 0..frame.cols {
+                    let index = row * frame.cols + col;
+                    if frame.highlighted[index] {
+                        screen.push_str(ANSI_BOLD);
+                        screen.push_str(ANSI_YELLOW);
+                        screen.push(char::from(frame.cells[index]));
+                        screen.push_str(ANSI_RESET);
+                    } else {
+                        screen.push(char::from(frame.cells[index]));
+                    }
+                }
+                screen.push_str("\x1b[K\n");
+            }
+            screen.push_str(&format!(
+                "{ANSI_DIM}frame {}/{}{ANSI_RESET} {}\x1b[K\n",
+                i + 1,
+                frames.len(),
+                frame.caption.as_deref().unwrap_or_default()
+            ));
+
+            // frames with fewer rows than the previous one leave its last rows behind.
+            screen.push_str("\x1b[J");
+            height = frame.rows + 1;
+
+            let _ = stdout.write_all(screen.as_bytes());
+            let _ = stdout.flush();
+            thread::sleep(TERMINAL_DELAY);
+        }
+    }
+
+    fn write_gif(
+        day: Day,
+        part: u8,
+        frames: &[&Frame],
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let rows = frames.iter().map(|f| f.rows).max().unwrap_or(0).max(1);
+        let cols = frames.iter().map(|f| f.cols).max().unwrap_or(0).max(1);
+        let cell_px = (GIF_SIZE / rows.max(cols)).clamp(1, MAX_CELL_PX);
+        let (width, height) = (cols * cell_px, rows * cell_px);
+
+        let dir = PathBuf::from("visualizations");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{day}-{part}.gif"));
+
+        let palette: Vec<u8> = PALETTE.iter().flatten().copied().collect();
+        let mut encoder = gif::Encoder::new(
+            File::create(&path)?,
+            u16::try_from(width)?,
+            u16::try_from(height)?,
+            &palette,
+        )?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        for frame in frames {
+            let mut pixels = vec![0; width * height];
+            for row in 0..frame.rows {
+                for col in 0..frame.cols {
+                    let color = color_index(frame, row * frame.cols + col);
+                    for y in row * cell_px..(row + 1) * cell_px {
+                        pixels[y * width + col * cell_px..][..cell_px].fill(color);
+                    }
+                }
+            }
+
+            encoder.write_frame(&gif::Frame {
+                width: u16::try_from(width)?,
+                height: u16::try_from(height)?,
+                buffer: Cow::Owned(pixels),
+                delay: GIF_DELAY,
+                ..gif::Frame::default()
+            })?;
+        }
+
+        Ok(path)
+    }
+
+    fn color_index(frame: &Frame, index: usize) -> u8 {
+        match frame.cells[index] {
+            _ if frame.highlighted[index] => 1,
+            b'.' | b' ' => 0,
+            // characters get one of the remaining colors of the palette.
+            c => 2 + c % (PALETTE.len() as u8 - 2),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Format, Frame};
+
+    #[test]
+    fn builds_frames() {
+        let frame = Frame::grid(2, 3, |row, col| if row == col { '#' } else { '.' })
+            .highlight([(1, 2), (5, 5)]);
+
+        assert_eq!(frame.cells, b"#...#.");
+        assert_eq!(frame.highlighted, [false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("gif".parse(), Ok(Format::Gif));
+        assert_eq!("Terminal".parse(), Ok(Format::Terminal));
+        assert!("png".parse::<Format>().is_err());
+        assert_eq!(Format::Gif.to_string(), "gif");
+    }
+
+    #[cfg(feature = "visualize")]
+    #[test]
+    fn samples_long_recordings() {
+        let mut recording = super::recording::Recording::new();
+        let mut built = 0;
+
+        for step in 0..2000 {
+            recording.push(|| {
+                built += 1;
+                Frame::grid(1, 1, |_, _| '.').caption(step.to_string())
+            });
+        }
+
+        // 2000 frames are recorded with a stride of 4.
+        assert_eq!(recording.emitted, 2000);
+        assert_eq!(recording.frames.len(), 500);
+        assert!(built < 1200);
+        assert!(recording
+            .frames
+            .iter()
+            .enumerate()
+            .all(|(i, frame)| frame.caption == Some((i * 4).to_string())));
+    }
+}